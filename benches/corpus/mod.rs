@@ -0,0 +1,65 @@
+use std::{env, fs};
+
+/// Messages to benchmark with. Set `RUSTYCOMMITS_BENCH_CORPUS` to a file of NUL separated
+/// messages from a real repository, e.g. `git log -n 100000 --format=%B%x00 > corpus`.
+/// Without it the same 100k synthetic conventional commits are generated on every run.
+pub fn messages() -> Vec<String> {
+    match env::var("RUSTYCOMMITS_BENCH_CORPUS") {
+        Ok(path) => fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("Could not read corpus {}: {}", path, err))
+            .split('\0')
+            .map(|message| message.trim_start_matches('\n').to_string())
+            .filter(|message| !message.is_empty())
+            .collect(),
+        Err(_) => synthetic(100_000),
+    }
+}
+
+const TOPICS: [&str; 6] = ["feat", "fix", "docs", "refactor", "chore", "test"];
+const SCOPES: [&str; 5] = ["api", "parser", "lexer", "cli", "deps"];
+const WORDS: [&str; 12] = [
+    "add", "remove", "handle", "users", "endpoint", "empty", "scope", "the", "config", "when",
+    "loading", "café",
+];
+
+// Linear congruential generator, keeps the corpus the same without a rand dependency
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) as usize) % bound
+    }
+
+    fn words(&mut self, count: usize) -> String {
+        (0..count)
+            .map(|_| WORDS[self.next(WORDS.len())])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn synthetic(count: usize) -> Vec<String> {
+    let mut rng = Lcg(42);
+    (0..count)
+        .map(|_| {
+            let mut message = TOPICS[rng.next(TOPICS.len())].to_string();
+            if rng.next(2) == 0 {
+                message.push_str(&format!("({})", SCOPES[rng.next(SCOPES.len())]));
+            }
+            let description_length = 3 + rng.next(6);
+            message.push_str(&format!(": {}", rng.words(description_length)));
+            for _ in 0..rng.next(3) {
+                let paragraph_length = 10 + rng.next(30);
+                message.push_str(&format!("\n\n{}", rng.words(paragraph_length)));
+            }
+            if rng.next(3) == 0 {
+                message.push_str(&format!("\n\nRefs: #{}", rng.next(10_000)));
+            }
+            message
+        })
+        .collect()
+}
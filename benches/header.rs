@@ -0,0 +1,41 @@
+mod corpus;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use parser_lib::CommitMessageParser;
+
+// What a history scan needs: topic, scope and the breaking flag, counted in commits per second
+fn header(c: &mut Criterion) {
+    let messages = corpus::messages();
+    let mut group = c.benchmark_group("header");
+    group.throughput(Throughput::Elements(messages.len() as u64));
+    group.sample_size(10);
+
+    group.bench_function("parse_full", |b| {
+        let mut parser = CommitMessageParser::new();
+        b.iter(|| {
+            for message in messages.iter() {
+                if let Ok(parsed) = parser.parse(message) {
+                    let topic = parsed.topic().map(|topic| topic.content_str());
+                    let scope = parsed.scope().map(|scope| scope.content_str());
+                    std::hint::black_box((topic, scope, parsed.is_breaking()));
+                }
+            }
+        })
+    });
+    group.bench_function("parse_header", |b| {
+        let mut parser = CommitMessageParser::new();
+        b.iter(|| {
+            for message in messages.iter() {
+                if let Ok(parsed) = parser.parse_header(message) {
+                    let topic = parsed.topic().map(|topic| topic.content_str());
+                    let scope = parsed.scope().map(|scope| scope.content_str());
+                    std::hint::black_box((topic, scope, parsed.is_breaking()));
+                }
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, header);
+criterion_main!(benches);
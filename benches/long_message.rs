@@ -0,0 +1,31 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use parser_lib::CommitMessageParser;
+
+// A squash merge with a pasted build log, body and footer parsing used to be quadratic
+fn squash_merge(lines: usize) -> String {
+    let mut message = String::from("fix(ci): stop the release job timing out\n\n");
+    for i in 0..lines {
+        message.push_str(&format!(
+            "[{:05}] step {} finished: ok (took 12ms) see #{}\n",
+            i,
+            i % 40,
+            i % 700
+        ));
+    }
+    message.push_str("\nRefs: #1234\nReviewed-by: someone\nSigned-off-by: someone else");
+    message
+}
+
+fn long_message(c: &mut Criterion) {
+    let message = squash_merge(10_000);
+    let mut group = c.benchmark_group("long_message");
+    group.throughput(Throughput::Bytes(message.len() as u64));
+    group.bench_function("10k_lines", |b| {
+        let mut parser = CommitMessageParser::new();
+        b.iter(|| std::hint::black_box(parser.parse(&message).unwrap()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, long_message);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+mod corpus;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use parser_lib::{CommitMessageParser, Processed};
+
+// `process` copies every message into an `Rc` and validates it, `parse_owned` only copies it.
+// `content_string` allocates, `parse` and `content_str` borrow from the message.
+//
+// The corpus is synthetic unless `RUSTYCOMMITS_BENCH_CORPUS` points to messages from a real
+// repository, see `corpus::messages`:
+//
+//     git -C path/to/repo log -n 100000 --format=%B%x00 > /tmp/corpus
+//     RUSTYCOMMITS_BENCH_CORPUS=/tmp/corpus cargo bench --bench parse
+fn parse(c: &mut Criterion) {
+    let messages = corpus::messages();
+    let bytes: usize = messages.iter().map(String::len).sum();
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(bytes as u64));
+    group.sample_size(10);
+
+    group.bench_function("process_validated", |b| {
+        let mut parser = CommitMessageParser::new();
+        b.iter(|| {
+            for message in messages.iter() {
+                if let Ok(Processed::Checked(parsed, errors)) = parser.process(message.clone()) {
+                    let topic = parsed.topic().map(|topic| topic.content_string());
+                    let description = parsed.description().map(|d| d.content_string());
+                    std::hint::black_box((topic, description, errors));
+                }
+            }
+        })
+    });
+    group.bench_function("parse_owned", |b| {
+        let mut parser = CommitMessageParser::new();
+        b.iter(|| {
+            for message in messages.iter() {
+                if let Ok(parsed) = parser.parse_owned(message.clone()) {
+                    let topic = parsed.topic().map(|topic| topic.content_string());
+                    let description = parsed.description().map(|d| d.content_string());
+                    std::hint::black_box((topic, description));
+                }
+            }
+        })
+    });
+    group.bench_function("parse_borrowed", |b| {
+        let mut parser = CommitMessageParser::new();
+        b.iter(|| {
+            for message in messages.iter() {
+                if let Ok(parsed) = parser.parse(message) {
+                    let topic = parsed.topic().map(|topic| topic.content_str());
+                    let description = parsed.description().map(|d| d.content_str());
+                    std::hint::black_box((topic, description));
+                }
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser_lib::{CommitMessageParser, ParseMode, Processed};
+
+fuzz_target!(|message: &str| {
+    for mode in [ParseMode::Strict, ParseMode::Lenient] {
+        let processed = CommitMessageParser::with_mode(mode).process(message.to_string());
+        if let Ok(Processed::Checked(parsed, _)) = processed {
+            let _ = parsed.header();
+            let _ = parsed.references();
+            let _ = parsed.revert();
+        }
+    }
+});
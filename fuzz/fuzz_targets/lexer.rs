@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser_lib::fuzzing;
+
+fuzz_target!(|message: &str| {
+    fuzzing::lex(message);
+});
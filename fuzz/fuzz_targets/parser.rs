@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parser_lib::{fuzzing, ParseMode};
+
+fuzz_target!(|data: &[u8]| {
+    for mode in [ParseMode::Strict, ParseMode::Lenient] {
+        let _ = fuzzing::parse_tokens(data, mode);
+    }
+});
@@ -0,0 +1,53 @@
+use std::{
+    env,
+    io::{self, BufReader},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use clap::Args;
+use parser_lib::{compose::Composer, git::staged_files, load_rules};
+
+use super::write_message;
+
+#[derive(Args)]
+pub struct ComposeArgs {
+    /// File to write the message into. The message is printed to stdout when omitted.
+    file: Option<PathBuf>,
+
+    /// Source of the message as passed to a prepare-commit-msg hook.
+    /// Nothing is prompted when the message already comes from somewhere (-m, merge, amend...).
+    source: Option<String>,
+
+    /// Commit SHA as passed to a prepare-commit-msg hook
+    commit: Option<String>,
+}
+
+pub fn run(args: ComposeArgs) -> ExitCode {
+    if args.source.is_some() {
+        return ExitCode::SUCCESS;
+    }
+
+    let rules = load_rules();
+    let staged = match env::current_dir() {
+        Ok(dir) if rules.scope().has_paths() => staged_files(&dir),
+        _ => Vec::new(),
+    };
+    let mut composer =
+        Composer::new(&rules, BufReader::new(io::stdin()), io::stderr()).with_staged_files(staged);
+    let message = match composer.compose() {
+        Ok(message) => message,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match write_message(&args.file, &format!("{}\n", message)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Could not write commit message: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::{env, process::ExitCode};
+
+use clap::Args;
+use parser_lib::git::{find_git_root, Hook, HookInstaller};
+
+#[derive(Args)]
+pub struct InstallHookArgs {
+    /// Also install a pre-push hook that lints every commit being pushed
+    #[arg(long)]
+    pre_push: bool,
+
+    /// Also install a prepare-commit-msg hook that runs `rustycommits compose`
+    #[arg(long)]
+    compose: bool,
+
+    /// Overwrite existing hooks
+    #[arg(long, conflicts_with = "chain")]
+    force: bool,
+
+    /// Keep existing hooks and run them before rustycommits
+    #[arg(long)]
+    chain: bool,
+}
+
+pub fn run(args: InstallHookArgs) -> ExitCode {
+    let root = match env::current_dir().ok().and_then(|dir| find_git_root(&dir)) {
+        Some(root) => root,
+        None => {
+            eprintln!("Not inside a git repository");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut hooks = vec![Hook::CommitMsg];
+    if args.pre_push {
+        hooks.push(Hook::PrePush);
+    }
+    if args.compose {
+        hooks.push(Hook::PrepareCommitMsg);
+    }
+
+    let installer = HookInstaller::for_repository(&root)
+        .force(args.force)
+        .chain(args.chain);
+    let mut exit_code = ExitCode::SUCCESS;
+    for hook in hooks {
+        match installer.install(hook) {
+            Ok(path) => println!("Installed {} hook into {:?}", hook.file_name(), path),
+            Err(err) => {
+                eprintln!("{}", err);
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+    exit_code
+}
@@ -0,0 +1,157 @@
+use std::{env, path::PathBuf, process::ExitCode};
+
+use clap::Args;
+use parser_lib::{
+    apply_fixes, git::staged_files, load_rules, CommitMessageParser, Fix, Processed, SyntaxError,
+    ValidationError,
+};
+
+use super::{read_message, write_message};
+
+#[derive(Args)]
+pub struct LintArgs {
+    /// File containing the commit message, e.g. the one git passes to a commit-msg hook.
+    /// The message is read from stdin when omitted.
+    file: Option<PathBuf>,
+
+    /// Apply suggested fixes. Rewrites FILE in place or prints the fixed message to stdout.
+    #[arg(long)]
+    fix: bool,
+
+    /// With --fix, also replace a scope that doesn't match the staged files with the one they imply
+    #[arg(long, requires = "fix")]
+    fix_scope: bool,
+}
+
+struct Lint {
+    errors: Vec<ValidationError>,
+    /// Scope mismatches and style issues, they don't fail the lint
+    warnings: Vec<ValidationError>,
+    fixes: Vec<Fix>,
+}
+
+// Replaces the scope with the one the staged files imply, only applied with --fix-scope
+fn scope_fix(warning: &ValidationError) -> Option<Fix> {
+    match warning {
+        ValidationError::ScopeMismatch { span, implied, .. } if implied.len() == 1 => {
+            Some(Fix::new(span.clone(), &implied[0]))
+        }
+        _ => None,
+    }
+}
+
+// Validates the message unless the rules ignore it, spans point into the whole message
+fn lint(
+    parser: &mut CommitMessageParser,
+    staged: &[String],
+    fix_scope: bool,
+    message: &str,
+) -> Result<Lint, SyntaxError> {
+    let Processed::Checked(parsed, errors) = parser.check(message)? else {
+        return Ok(Lint {
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            fixes: Vec::new(),
+        });
+    };
+    let mut warnings: Vec<ValidationError> = parser
+        .rules()
+        .scope()
+        .validate_paths(&parsed, staged)
+        .into_iter()
+        .collect();
+    warnings.extend(parser.rules().style_warnings(&parsed));
+    // Style fixes only remove whitespace, so --fix applies them even though they're warnings
+    let mut fixes: Vec<Fix> = errors
+        .iter()
+        .chain(warnings.iter())
+        .filter_map(|error| error.fix())
+        .collect();
+    if fix_scope {
+        fixes.extend(warnings.iter().filter_map(scope_fix));
+    }
+    Ok(Lint {
+        errors,
+        warnings,
+        fixes,
+    })
+}
+
+// `flags` are the ones that apply the fix
+fn report(
+    message: &str,
+    error: &ValidationError,
+    parser: &CommitMessageParser,
+    fix: Option<Fix>,
+    flags: &str,
+) {
+    eprintln!("{}", error);
+    if let Some(span) = error.span() {
+        let position = parser.rules().length_unit().position(message, span.start);
+        eprintln!("  at {}", position);
+    }
+    match fix {
+        Some(fix) if fix.replacement.is_empty() => eprintln!("  (fixable with {})", flags),
+        Some(fix) => eprintln!("  (fixable with {}: '{}')", flags, fix.replacement),
+        None => (),
+    }
+}
+
+pub fn run(args: LintArgs) -> ExitCode {
+    let mut commit_message = match read_message(&args.file) {
+        Ok(message) => message,
+        Err(err) => {
+            eprintln!("Could not read commit message: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut parser = CommitMessageParser::builder().rules(load_rules()).build();
+    // Only a commit-msg hook's file is the commit being made, messages on stdin are usually
+    // existing commits, e.g. from the pre-push hook, which the staged files say nothing about
+    let staged = match env::current_dir() {
+        Ok(dir) if args.file.is_some() && parser.rules().scope().has_paths() => staged_files(&dir),
+        _ => Vec::new(),
+    };
+    let mut result = match lint(&mut parser, &staged, args.fix_scope, &commit_message) {
+        Ok(result) => result,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.fix {
+        commit_message = apply_fixes(&commit_message, &result.fixes);
+        if let Err(err) = write_message(&args.file, &commit_message) {
+            eprintln!("Could not write fixed commit message: {}", err);
+            return ExitCode::FAILURE;
+        }
+        result = match lint(&mut parser, &staged, args.fix_scope, &commit_message) {
+            Ok(result) => result,
+            Err(err) => {
+                eprintln!("{}", err);
+                return ExitCode::FAILURE;
+            }
+        };
+    }
+
+    for warning in result.warnings.iter() {
+        match warning.fix() {
+            Some(fix) => report(&commit_message, warning, &parser, Some(fix), "--fix"),
+            None => {
+                let fix = scope_fix(warning);
+                report(&commit_message, warning, &parser, fix, "--fix --fix-scope");
+            }
+        }
+    }
+    for error in result.errors.iter() {
+        report(&commit_message, error, &parser, error.fix(), "--fix");
+    }
+
+    if result.errors.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
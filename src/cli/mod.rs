@@ -0,0 +1,88 @@
+mod compose;
+mod install_hook;
+mod lint;
+mod stats;
+
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use parser_lib::git::{CleanupMode, MessageCleanup};
+
+pub use self::compose::ComposeArgs;
+pub use self::install_hook::InstallHookArgs;
+pub use self::lint::LintArgs;
+pub use self::stats::StatsArgs;
+
+#[derive(Parser)]
+#[command(
+    version,
+    about = "Lint commit messages against the Conventional Commits spec",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    lint: LintArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Lint a commit message (default)
+    Lint(LintArgs),
+    /// Interactively write a commit message
+    Compose(ComposeArgs),
+    /// Install git hooks that run rustycommits
+    InstallHook(InstallHookArgs),
+    /// Report how well a range of commits follows the rules
+    Stats(StatsArgs),
+}
+
+impl Cli {
+    pub fn run(self) -> ExitCode {
+        match self.command {
+            Some(Command::Lint(args)) => lint::run(args),
+            Some(Command::Compose(args)) => compose::run(args),
+            Some(Command::InstallHook(args)) => install_hook::run(args),
+            Some(Command::Stats(args)) => stats::run(args),
+            None => lint::run(self.lint),
+        }
+    }
+}
+
+// A commit-msg hook gets the file before git cleans it up, so it's cleaned up the same way.
+// Messages on stdin, e.g. from `git log`, are already clean and may start lines with '#',
+// only surrounding whitespace is removed from them.
+fn read_message(file: &Option<PathBuf>) -> io::Result<String> {
+    match file {
+        Some(path) => {
+            let message = fs::read_to_string(path)?;
+            let cleanup = match env::current_dir() {
+                Ok(dir) => MessageCleanup::from_git_config(&dir),
+                Err(_) => MessageCleanup::default(),
+            };
+            Ok(cleanup.apply(&message))
+        }
+        None => {
+            let mut commit_message = String::new();
+            io::stdin().lock().read_to_string(&mut commit_message)?;
+            Ok(MessageCleanup::new(CleanupMode::Whitespace, "#").apply(&commit_message))
+        }
+    }
+}
+
+fn write_message(file: &Option<PathBuf>, message: &str) -> io::Result<()> {
+    match file {
+        Some(path) => fs::write(path, message),
+        None => {
+            print!("{}", message);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::{env, process::ExitCode};
+
+use clap::Args;
+use parser_lib::{git::read_log, load_rules, CommitMessageParser, Stats};
+
+#[derive(Args)]
+pub struct StatsArgs {
+    /// Commits to report on, anything `git log` accepts, e.g. `v1.0..HEAD`
+    #[arg(default_value = "HEAD")]
+    range: String,
+
+    /// Print the report as JSON instead of tables
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn run(args: StatsArgs) -> ExitCode {
+    let entries = match env::current_dir().and_then(|dir| read_log(&dir, &args.range)) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("Could not read git log: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut parser = CommitMessageParser::builder().rules(load_rules()).build();
+    let stats = Stats::collect(&entries, &mut parser);
+    if args.json {
+        match serde_json::to_string_pretty(&stats) {
+            Ok(json) => println!("{}", json),
+            Err(err) => {
+                eprintln!("Could not serialize stats: {}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        print!("{}", stats);
+    }
+    ExitCode::SUCCESS
+}
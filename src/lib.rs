@@ -1 +1,46 @@
-pub mod parser_lib;
+//! Parses and lints commit messages following the
+//! [Conventional Commits](https://www.conventionalcommits.org) format.
+//!
+//! ```
+//! use parser_lib::{CommitMessageParser, ValidationRules};
+//!
+//! let mut parser = CommitMessageParser::builder()
+//!     .rules(ValidationRules::default())
+//!     .build();
+//! let (message, errors) = parser
+//!     .process("feat(api): add users endpoint\n\nRefs: #12".to_string())
+//!     .unwrap()
+//!     .checked()
+//!     .unwrap();
+//! assert_eq!(message.topic().unwrap().content_string(), "feat");
+//! assert_eq!(message.scope().unwrap().content_string(), "api");
+//! assert_eq!(message.footers().len(), 1);
+//! assert!(errors.is_empty());
+//! ```
+
+mod parser_lib;
+
+// Lexer tokens stay internal. `Symbol` and `UnexpectedToken` expose their text and spans.
+pub use parser_lib::{
+    apply_fixes, cancel_reverts, load_rules, AllowedEntry, AllowedList, CommitMessage,
+    CommitMessageParser, CommitMessageParserBuilder, Conformance, DescriptionRules, Fix,
+    IssueReference, LengthUnit, ParseMode, Position, Preset, Processed, Release, Revert,
+    ScopeRules, Stats, StyleDiagnostic, StyleIssue, Symbol, SyntaxError, TextCase, TopicRules,
+    UnexpectedToken, ValidationError, ValidationRules, Validator,
+};
+
+/// Helpers for working with git: hooks, message cleanup, history and staged files
+pub mod git {
+    pub use crate::parser_lib::{
+        find_git_root, parse_trailers, read_log, staged_files, CleanupMode, Hook, HookError,
+        HookInstaller, LogEntry, MessageCleanup, Trailer,
+    };
+}
+
+/// Interactive prompts the `compose` command is built on
+pub mod compose {
+    pub use crate::parser_lib::Composer;
+}
+
+#[cfg(fuzzing)]
+pub use parser_lib::fuzzing;
@@ -1,17 +1,10 @@
-use parser_lib::parser_lib::CommitMessageParser;
-use std::io::{self, Read};
-fn main() {
-    let stdin = io::stdin();
-    let mut stdin = stdin.lock();
-    let mut commit_message = String::new();
+mod cli;
 
-    while let Ok(bytes) = stdin.read_to_string(&mut commit_message) {
-        if bytes == 0 {
-            break;
-        }
-    }
+use clap::Parser;
+use std::process::ExitCode;
 
-    let mut parser = CommitMessageParser::new();
-    let parsed = parser.process(commit_message).unwrap();
-    println!("{:?}", parsed);
+use cli::Cli;
+
+fn main() -> ExitCode {
+    Cli::parse().run()
 }
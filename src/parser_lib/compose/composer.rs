@@ -0,0 +1,386 @@
+use std::{
+    io::{self, BufRead, Write},
+    ops::Range,
+};
+
+use crate::parser_lib::{
+    validation::Validator, CommitMessageParser, ValidationError, ValidationRules,
+};
+
+/// Builds a commit message by prompting for each part of it.
+/// Every answer is validated against the rules before moving on to the next part.
+pub struct Composer<'a, R: BufRead, W: Write> {
+    rules: &'a ValidationRules,
+    parser: CommitMessageParser,
+    /// Files the scope is checked against when the rules map paths to scopes
+    staged_files: Vec<String>,
+    input: R,
+    output: W,
+}
+
+impl<'a, R: BufRead, W: Write> Composer<'a, R, W> {
+    pub fn new(rules: &'a ValidationRules, input: R, output: W) -> Self {
+        Self {
+            rules,
+            parser: CommitMessageParser::new(),
+            staged_files: Vec::new(),
+            input,
+            output,
+        }
+    }
+
+    pub fn with_staged_files(mut self, files: Vec<String>) -> Self {
+        self.staged_files = files;
+        self
+    }
+
+    pub fn compose(&mut self) -> io::Result<String> {
+        let topic = self.prompt_topic()?;
+        let scope = self.prompt_scope(&topic)?;
+        let header_start = match &scope {
+            Some(scope) => format!("{}({})", topic, scope),
+            None => topic,
+        };
+
+        let description = self.prompt_description(&format!("{}: ", header_start))?;
+        let body = self.prompt_body()?;
+        let breaking_change = self.prompt_breaking_change()?;
+
+        let bang = if breaking_change.is_some() { "!" } else { "" };
+        let mut message = format!("{}{}: {}", header_start, bang, description);
+        if !body.is_empty() {
+            message = format!("{}\n\n{}", message, body);
+        }
+
+        let mut footers = self.prompt_footers(&message)?;
+        if let Some(breaking_change) = breaking_change {
+            footers.insert(0, format!("BREAKING-CHANGE: {}", breaking_change));
+        }
+        if !footers.is_empty() {
+            message = format!("{}\n\n{}", message, footers.join("\n"));
+        }
+
+        let errors = self.check(&message, 0..message.len(), true);
+        if !errors.is_empty() {
+            for error in errors.iter() {
+                writeln!(self.output, "{}", error)?;
+            }
+            if !self.confirm("The message doesn't pass validation. Use it anyway?")? {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Commit message doesn't pass validation",
+                ));
+            }
+        }
+
+        Ok(message)
+    }
+
+    fn prompt_topic(&mut self) -> io::Result<String> {
+        let allowed = self.rules.topic().allowed().clone();
+        writeln!(self.output, "Select the type of change:")?;
+        for (i, entry) in allowed.iter().enumerate() {
+            writeln!(self.output, "  {:>2}) {}", i + 1, entry)?;
+        }
+
+        loop {
+            let answer = self.prompt("Type")?;
+            // Allow picking from the list by number
+            let topic = match answer.parse::<usize>() {
+                Ok(i) if (1..=allowed.names().len()).contains(&i) => {
+                    allowed.names()[i - 1].to_string()
+                }
+                _ => answer,
+            };
+
+            let candidate = format!("{}: x", topic);
+            let errors = self.check(&candidate, 0..topic.len(), false);
+            if self.report(&errors)? {
+                return Ok(topic);
+            }
+        }
+    }
+
+    fn prompt_scope(&mut self, topic: &str) -> io::Result<Option<String>> {
+        let allowed = self.rules.scope().allowed().clone();
+        if !allowed.is_empty() {
+            writeln!(self.output, "Scopes:")?;
+            for entry in allowed.iter() {
+                writeln!(self.output, "  {}", entry)?;
+            }
+        }
+
+        // Pressing enter picks the scope of the staged files when they all have the same one
+        let implied = self.rules.scope().scopes_for(&self.staged_files);
+        let required = self.rules.scope().required();
+        let question = match (implied.as_slice(), required) {
+            ([scope], true) => format!("Scope (enter for '{}')", scope),
+            ([scope], false) => format!("Scope (optional, enter for '{}')", scope),
+            (_, true) => "Scope".to_string(),
+            (_, false) => "Scope (optional)".to_string(),
+        };
+        loop {
+            let mut scope = self.prompt(&question)?;
+            if scope.is_empty() {
+                match implied.as_slice() {
+                    [implied] => scope = implied.clone(),
+                    _ if required => {
+                        writeln!(self.output, "{}", ValidationError::MissingScope)?;
+                        continue;
+                    }
+                    _ => return Ok(None),
+                }
+            }
+
+            let candidate = format!("{}({}): x", topic, scope);
+            let span = topic.len() + 1..topic.len() + 1 + scope.len();
+            let errors = self.check(&candidate, span, false);
+            if self.report(&errors)? {
+                return Ok(Some(scope));
+            }
+        }
+    }
+
+    fn prompt_breaking_change(&mut self) -> io::Result<Option<String>> {
+        if !self.confirm("Is this a breaking change?")? {
+            return Ok(None);
+        }
+
+        loop {
+            let description = self.prompt("Describe the breaking change")?;
+            if !description.is_empty() {
+                return Ok(Some(description));
+            }
+            writeln!(self.output, "A breaking change needs a description")?;
+        }
+    }
+
+    fn prompt_description(&mut self, header_start: &str) -> io::Result<String> {
+        loop {
+            let description = self.prompt("Short description")?;
+            if description.is_empty() {
+                writeln!(self.output, "The description can't be empty")?;
+                continue;
+            }
+
+            let candidate = format!("{}{}", header_start, description);
+            let errors = self.check(&candidate, header_start.len()..candidate.len(), false);
+            if self.report(&errors)? {
+                return Ok(description);
+            }
+        }
+    }
+
+    fn prompt_body(&mut self) -> io::Result<String> {
+        writeln!(
+            self.output,
+            "Longer description (optional, finish with a line with just '.'):"
+        )?;
+        // Empty lines separate paragraphs, so the body ends with a line of its own
+        let mut lines: Vec<String> = Vec::new();
+        loop {
+            let line = self.read_raw_line()?;
+            if line == "." {
+                break;
+            }
+            lines.push(line);
+        }
+
+        // Blank lines around the body would end up next to the blank line separating it
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        let start = lines
+            .iter()
+            .position(|line| !line.trim().is_empty())
+            .unwrap_or(lines.len());
+        Ok(lines[start..].join("\n"))
+    }
+
+    fn prompt_footers(&mut self, message: &str) -> io::Result<Vec<String>> {
+        writeln!(
+            self.output,
+            "Footers, e.g. 'Refs: #123' (optional, finish with an empty line):"
+        )?;
+        let mut footers: Vec<String> = Vec::new();
+        loop {
+            let footer = self.read_line()?;
+            if footer.is_empty() {
+                return Ok(footers);
+            }
+
+            let candidate = format!("{}\n\n{}", message, footer);
+            let parsed_footers = self
+                .parser
+                .parse(&candidate)
+                .ok()
+                .and_then(|parsed| parsed.footers);
+            if parsed_footers.is_none() {
+                writeln!(
+                    self.output,
+                    "'{}' is not a footer. Expected 'Key: value' or 'Key #value'",
+                    footer
+                )?;
+                continue;
+            }
+
+            let span = candidate.len() - footer.len()..candidate.len();
+            let errors = self.check(&candidate, span, false);
+            if self.report(&errors)? {
+                footers.push(footer);
+            }
+        }
+    }
+
+    // Returns the syntax and validation errors of the candidate message.
+    // Unless `all` is set, only errors pointing inside `span` are returned.
+    fn check(&mut self, candidate: &str, span: Range<usize>, all: bool) -> Vec<String> {
+        match self.parser.parse(candidate) {
+            Ok(parsed) => Validator::validate(&parsed, self.rules)
+                .into_iter()
+                .chain(
+                    self.rules
+                        .scope()
+                        .validate_paths(&parsed, &self.staged_files),
+                )
+                .filter(|error| match error.span() {
+                    Some(error_span) => all || span.contains(&error_span.start),
+                    None => all,
+                })
+                .map(|error| error.to_string())
+                .collect(),
+            Err(err) => vec![err.to_string()],
+        }
+    }
+
+    // Prints the errors, returns true when there were none
+    fn report(&mut self, errors: &[String]) -> io::Result<bool> {
+        for error in errors.iter() {
+            writeln!(self.output, "{}", error)?;
+        }
+        Ok(errors.is_empty())
+    }
+
+    fn confirm(&mut self, question: &str) -> io::Result<bool> {
+        let answer = self.prompt(&format!("{} [y/N]", question))?;
+        Ok(matches!(answer.to_lowercase().as_str(), "y" | "yes"))
+    }
+
+    fn prompt(&mut self, question: &str) -> io::Result<String> {
+        write!(self.output, "{}: ", question)?;
+        self.output.flush()?;
+        self.read_line()
+    }
+
+    fn read_line(&mut self) -> io::Result<String> {
+        Ok(self.read_raw_line()?.trim().to_string())
+    }
+
+    // Reads a line without its line ending, keeping the indentation
+    fn read_raw_line(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Input ended before the commit message was complete",
+            ));
+        }
+        Ok(line.trim_end_matches(['\n', '\r']).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser_lib::test_utils::rules_from;
+
+    fn compose(input: &str) -> (io::Result<String>, String) {
+        let rules = ValidationRules::default();
+        let mut output: Vec<u8> = Vec::new();
+        let result = Composer::new(&rules, input.as_bytes(), &mut output).compose();
+        (result, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn should_compose_header_only_message() {
+        let (result, _) = compose("feat\napi\nadd users endpoint\n.\nn\n\n");
+        assert_eq!(result.unwrap(), "feat(api): add users endpoint");
+    }
+
+    #[test]
+    fn should_pick_topic_by_number() {
+        let (result, output) = compose("5\n\nadd users endpoint\n.\nn\n\n");
+        assert!(output.contains(" 5) 'feat' — A new feature"));
+        assert_eq!(result.unwrap(), "feat: add users endpoint");
+    }
+
+    #[test]
+    fn should_ask_again_on_unknown_topic() {
+        let (result, output) = compose("fature\nfeat\n\nadd users endpoint\n.\nn\n\n");
+        assert!(output.contains("Did you mean 'feat' — A new feature?"));
+        assert_eq!(result.unwrap(), "feat: add users endpoint");
+    }
+
+    #[test]
+    fn should_compose_full_message() {
+        let input = "fix\nparsing\nhandle footers\nafter the header\n.\ny\n\
+                     tokens are now spans\nRefs: #12\nnot a footer\n\n";
+        let (result, output) = compose(input);
+        assert!(output.contains("'not a footer' is not a footer"));
+        assert_eq!(
+            result.unwrap(),
+            "fix(parsing)!: handle footers\n\nafter the header\n\n\
+             BREAKING-CHANGE: tokens are now spans\nRefs: #12"
+        );
+    }
+
+    #[test]
+    fn should_keep_paragraphs_and_indentation_of_body() {
+        let input = "docs\n\nexplain config lookup\n\nThe config is read from:\n\n    \
+                     rclint.toml\n\nin the closest parent directory.\n\n.\nn\n\n";
+        let (result, _) = compose(input);
+        assert_eq!(
+            result.unwrap(),
+            "docs: explain config lookup\n\nThe config is read from:\n\n    rclint.toml\n\n\
+             in the closest parent directory."
+        );
+    }
+
+    #[test]
+    fn should_suggest_scope_of_staged_files() {
+        let rules = rules_from("scope.paths.'api/**' = 'api'");
+        let compose = |input: &str| {
+            let mut output: Vec<u8> = Vec::new();
+            let result = Composer::new(&rules, input.as_bytes(), &mut output)
+                .with_staged_files(vec!["api/users.rs".to_string()])
+                .compose();
+            (result, String::from_utf8(output).unwrap())
+        };
+
+        let (result, output) = compose("feat\n\nadd users\n.\nn\n\n");
+        assert!(output.contains("Scope (optional, enter for 'api')"));
+        assert_eq!(result.unwrap(), "feat(api): add users");
+
+        let (result, output) = compose("feat\ncli\napi\nadd users\n.\nn\n\n");
+        assert!(output.contains("Scope 'cli' doesn't match the changed files. Expected api."));
+        assert_eq!(result.unwrap(), "feat(api): add users");
+    }
+
+    #[test]
+    fn should_ask_again_on_missing_required_scope() {
+        let rules = rules_from("scope.required = true");
+        let mut output: Vec<u8> = Vec::new();
+        let input = "feat\n\napi\nadd users\n.\nn\n\n";
+        let result = Composer::new(&rules, input.as_bytes(), &mut output).compose();
+        let output = String::from_utf8(output).unwrap();
+        assert!(!output.contains("Scope (optional)"));
+        assert!(output.contains("Missing scope."));
+        assert_eq!(result.unwrap(), "feat(api): add users");
+    }
+
+    #[test]
+    fn should_fail_when_input_ends_early() {
+        let (result, _) = compose("feat\n");
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
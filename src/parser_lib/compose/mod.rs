@@ -0,0 +1,3 @@
+mod composer;
+
+pub use self::composer::Composer;
@@ -1,45 +1,279 @@
+use std::{io, ops::Range, path::PathBuf};
+
 use thiserror::Error;
 
-use super::lexing::types::Token;
+use super::{
+    lexing::types::Token,
+    parsing::types::{StyleIssue, TextCase},
+    text_length::{LengthUnit, Position},
+    validation::{
+        rules::{AllowedEntry, AllowedList},
+        Fix,
+    },
+};
 
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum SyntaxError {
-    #[error("Syntax error: Unexpected token '{}' at index {}. Expected {1}.", .0.get_value(), .0.get_start_index())]
-    UnexpectedToken(Token, String),
-    #[error("Syntax error: Unexpted end of file")]
+    #[error("Syntax error: Unexpected token '{}' at {}. Expected {}.", .0.found(), .0.position(), .0.expected())]
+    UnexpectedToken(UnexpectedToken),
+    #[error("Syntax error: Unexpected end of file.")]
     UnexpectedEndOfFile,
 }
 
+/// A token the parser didn't expect, and what it expected instead
+///
+/// ```
+/// use parser_lib::{CommitMessageParser, SyntaxError};
+///
+/// let Err(SyntaxError::UnexpectedToken(unexpected)) = CommitMessageParser::new().parse("feat:x")
+/// else {
+///     panic!("expected a syntax error");
+/// };
+/// assert_eq!(unexpected.found(), "x");
+/// assert_eq!(unexpected.expected(), "a space");
+/// assert_eq!(unexpected.span(), 5..6);
+/// assert_eq!(unexpected.position().column, 6);
+/// ```
+#[derive(Debug)]
+pub struct UnexpectedToken {
+    token: Token<'static>,
+    expected: String,
+    // Unit the column is counted in
+    unit: LengthUnit,
+}
+
+impl UnexpectedToken {
+    /// Text of the unexpected token
+    pub fn found(&self) -> &str {
+        self.token.as_str()
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// Byte range of the token in the message
+    pub fn span(&self) -> Range<usize> {
+        self.token.get_start_index()..self.token.get_end_index()
+    }
+
+    pub fn position(&self) -> Position {
+        position_of(&self.token, self.unit)
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    #[error("Validation error: Unknown topic '{value}'. {}", expected_one_of(.suggestion, .allowed))]
+    UnknownTopic {
+        value: String,
+        span: Range<usize>,
+        suggestion: Option<AllowedEntry>,
+        allowed: AllowedList,
+    },
+    #[error("Validation error: Unknown scope '{value}'. {}", expected_one_of(.suggestion, .allowed))]
+    UnknownScope {
+        value: String,
+        span: Range<usize>,
+        suggestion: Option<AllowedEntry>,
+        allowed: AllowedList,
+    },
+    #[error("Validation error: Footer '{key}' is not allowed. Expected one of: {}.", .allowed.join(", "))]
+    FooterKeyNotAllowed {
+        key: String,
+        span: Range<usize>,
+        allowed: Vec<String>,
+    },
+    #[error("Validation error: Footer key '{key}' should be {expected}.")]
+    FooterKeyCase {
+        key: String,
+        span: Range<usize>,
+        expected: TextCase,
+    },
+    #[error("Validation error: Footer '{key}' spans multiple lines.")]
+    MultiLineFooter { key: String, span: Range<usize> },
+    #[error("Validation error: Value '{value}' of footer '{key}' doesn't match '{pattern}'.")]
+    InvalidFooterValue {
+        key: String,
+        value: String,
+        span: Range<usize>,
+        pattern: String,
+    },
+    #[error("Validation error: Footer '{key}' is allowed {max} time(s), found {count}.")]
+    TooManyFooters {
+        key: String,
+        count: usize,
+        max: usize,
+    },
+    #[error("Validation error: Missing footer '{key}'{}.", .topic.as_ref().map(|t| format!(" required for '{}' commits", t)).unwrap_or_default())]
+    MissingFooter { key: String, topic: Option<String> },
+    /// A warning, the staged files only hint at the scope
+    #[error("Warning: Scope '{value}' doesn't match the changed files. Expected {}.", .implied.join(" or "))]
+    ScopeMismatch {
+        value: String,
+        span: Range<usize>,
+        implied: Vec<String>,
+    },
+    #[error("Validation error: Missing scope.")]
+    MissingScope,
+    #[error("Validation error: Missing body.")]
+    MissingBody,
+    #[error("Validation error: Missing issue reference{}.", if .patterns.is_empty() { String::new() } else { format!(" matching one of: {}", .patterns.join(", ")) })]
+    MissingReference { patterns: Vec<String> },
+    #[error("Validation error: Description is {length} {unit} long, the maximum is {max}.")]
+    DescriptionTooLong {
+        length: usize,
+        max: usize,
+        unit: LengthUnit,
+        span: Range<usize>,
+    },
+    #[error("Validation error: {issue}.")]
+    Style {
+        issue: StyleIssue,
+        span: Range<usize>,
+    },
+    /// A warning, unless `style_errors` is set the lenient parse mode only reports whitespace
+    #[error("Warning: {issue}.")]
+    StyleWarning {
+        issue: StyleIssue,
+        span: Range<usize>,
+    },
+}
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum HookError {
+    #[error("Hook error: {0:?} already exists. Use --force to overwrite it or --chain to run it before rustycommits.")]
+    AlreadyExists(PathBuf),
+    #[error("Hook error: {0}")]
+    Io(#[from] io::Error),
+}
+
+fn position_of(token: &Token, unit: LengthUnit) -> Position {
+    unit.position(token.get_source(), token.get_start_index())
+}
+
+fn expected_one_of(suggestion: &Option<AllowedEntry>, allowed: &AllowedList) -> String {
+    match suggestion {
+        Some(entry) => format!("Did you mean {}?", entry),
+        None if allowed.is_empty() => "None are allowed.".to_string(),
+        None => format!("Expected one of:\n{}", allowed),
+    }
+}
+
+impl ValidationError {
+    /// Span of the offending part of the message, if the error points at one
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            ValidationError::UnknownTopic { span, .. } => Some(span.clone()),
+            ValidationError::UnknownScope { span, .. } => Some(span.clone()),
+            ValidationError::ScopeMismatch { span, .. } => Some(span.clone()),
+            ValidationError::FooterKeyNotAllowed { span, .. } => Some(span.clone()),
+            ValidationError::FooterKeyCase { span, .. } => Some(span.clone()),
+            ValidationError::MultiLineFooter { span, .. } => Some(span.clone()),
+            ValidationError::InvalidFooterValue { span, .. } => Some(span.clone()),
+            ValidationError::TooManyFooters { .. } => None,
+            ValidationError::MissingFooter { .. } => None,
+            ValidationError::MissingScope => None,
+            ValidationError::MissingBody => None,
+            ValidationError::MissingReference { .. } => None,
+            ValidationError::Style { span, .. } => Some(span.clone()),
+            ValidationError::StyleWarning { span, .. } => Some(span.clone()),
+            ValidationError::DescriptionTooLong { span, .. } => Some(span.clone()),
+        }
+    }
+
+    /// Stable name of the rule that failed, e.g. `unknown-topic`
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            ValidationError::UnknownTopic { .. } => "unknown-topic",
+            ValidationError::UnknownScope { .. } => "unknown-scope",
+            ValidationError::ScopeMismatch { .. } => "scope-mismatch",
+            ValidationError::FooterKeyNotAllowed { .. } => "footer-key-not-allowed",
+            ValidationError::FooterKeyCase { .. } => "footer-key-case",
+            ValidationError::MultiLineFooter { .. } => "multi-line-footer",
+            ValidationError::InvalidFooterValue { .. } => "invalid-footer-value",
+            ValidationError::TooManyFooters { .. } => "too-many-footers",
+            ValidationError::MissingFooter { .. } => "missing-footer",
+            ValidationError::MissingScope => "missing-scope",
+            ValidationError::MissingBody => "missing-body",
+            ValidationError::MissingReference { .. } => "missing-reference",
+            ValidationError::DescriptionTooLong { .. } => "description-too-long",
+            ValidationError::Style { .. } => "style",
+            ValidationError::StyleWarning { .. } => "style",
+        }
+    }
+
+    pub fn fix(&self) -> Option<Fix> {
+        match self {
+            ValidationError::UnknownTopic {
+                span,
+                suggestion: Some(entry),
+                ..
+            }
+            | ValidationError::UnknownScope {
+                span,
+                suggestion: Some(entry),
+                ..
+            } => Some(Fix::new(span.clone(), entry.name())),
+            ValidationError::Style { span, .. } | ValidationError::StyleWarning { span, .. } => {
+                Some(Fix::new(span.clone(), ""))
+            }
+            _ => None,
+        }
+    }
+}
+
 impl SyntaxError {
-    pub fn expected_newline(token: Token) -> Self {
-        SyntaxError::UnexpectedToken(token, "'\n'".to_string())
+    /// Where in the message the error is, columns counted in `unit`
+    pub fn position(&self, unit: LengthUnit) -> Option<Position> {
+        match self {
+            SyntaxError::UnexpectedToken(unexpected) => Some(position_of(&unexpected.token, unit)),
+            SyntaxError::UnexpectedEndOfFile => None,
+        }
+    }
+
+    /// Reports the position with columns counted in `unit`, e.g. the configured length unit
+    pub fn in_unit(self, unit: LengthUnit) -> Self {
+        match self {
+            SyntaxError::UnexpectedToken(unexpected) => {
+                SyntaxError::UnexpectedToken(UnexpectedToken { unit, ..unexpected })
+            }
+            error => error,
+        }
     }
 
-    pub fn expected_string(token: Token) -> Self {
-        SyntaxError::UnexpectedToken(token, "a string".to_string())
+    pub(crate) fn unexpected(token: Token<'_>, expected: &str) -> Self {
+        SyntaxError::UnexpectedToken(UnexpectedToken {
+            token: token.into_owned(),
+            expected: expected.to_string(),
+            unit: LengthUnit::Chars,
+        })
     }
 
-    pub fn expected_colon(token: Token) -> Self {
-        SyntaxError::UnexpectedToken(token, "':'".to_string())
+    pub(crate) fn expected_string(token: Token<'_>) -> Self {
+        SyntaxError::unexpected(token, "a string")
     }
 
-    pub fn expected_space(token: Token) -> Self {
-        SyntaxError::UnexpectedToken(token, "a space".to_string())
+    pub(crate) fn expected_colon(token: Token<'_>) -> Self {
+        SyntaxError::unexpected(token, "':'")
     }
 
-    pub fn expected_parent_open(token: Token) -> Self {
-        SyntaxError::UnexpectedToken(token, "'('".to_string())
+    pub(crate) fn expected_space(token: Token<'_>) -> Self {
+        SyntaxError::unexpected(token, "a space")
     }
 
-    pub fn expected_parent_close(token: Token) -> Self {
-        SyntaxError::UnexpectedToken(token, "')'".to_string())
+    pub(crate) fn expected_parent_close(token: Token<'_>) -> Self {
+        SyntaxError::unexpected(token, "')'")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser_lib::test_utils::TestTokenBuilder;
+    use crate::parser_lib::{lexing::Lexer, test_utils::TestTokenBuilder};
     #[test]
     fn should_parse_error_correctly() {
         let token = TestTokenBuilder::new()
@@ -48,10 +282,10 @@ mod tests {
             .0
             .pop()
             .unwrap();
-        let error = SyntaxError::UnexpectedToken(token, "':'".to_string());
+        let error = SyntaxError::unexpected(token, "':'");
         assert_eq!(
             error.to_string(),
-            "Syntax error: Unexpected token 'test' at index 0. Expected ':'."
+            "Syntax error: Unexpected token 'test' at line 1, column 1. Expected ':'."
         );
     }
 
@@ -62,20 +296,32 @@ mod tests {
             .colon()
             .word("value")
             .generate_vec();
-        let error = SyntaxError::UnexpectedToken(token[2].clone(), "a space".to_string());
+        let error = SyntaxError::unexpected(token[2].clone(), "a space");
         assert_eq!(
             error.to_string(),
-            "Syntax error: Unexpected token 'value' at index 5. Expected a space."
+            "Syntax error: Unexpected token 'value' at line 1, column 6. Expected a space."
         );
     }
 
     #[test]
     fn should_display_newline_char_correctly() {
         let (token, _) = TestTokenBuilder::new().word("test").generate_vec();
-        let error = SyntaxError::UnexpectedToken(token[0].clone(), "'\n'".to_string());
+        let error = SyntaxError::unexpected(token[0].clone(), "'\n'");
         assert_eq!(
             error.to_string(),
-            "Syntax error: Unexpected token 'test' at index 0. Expected '\n'."
+            "Syntax error: Unexpected token 'test' at line 1, column 1. Expected '\n'."
         );
     }
+
+    #[test]
+    fn should_report_column_in_unit() {
+        let token = Lexer::new("fix: 👍🏽 x")
+            .find(|token| token.get_value() == "x")
+            .unwrap();
+        let error = SyntaxError::unexpected(token, "':'");
+        assert!(error.to_string().contains("column 9."));
+        let error = error.in_unit(LengthUnit::Graphemes);
+        assert!(error.to_string().contains("column 8."));
+        assert_eq!(error.position(LengthUnit::Bytes).unwrap().column, 15);
+    }
 }
@@ -0,0 +1,76 @@
+//! Entry points for the fuzz targets in `fuzz/`, only built with `--cfg fuzzing`
+
+use std::rc::Rc;
+
+use super::{
+    lexing::{types::Token, types::WordDetails, Lexer},
+    parsing::{types::ParseMode, Parser},
+    SlicableRcString, SyntaxError,
+};
+
+/// Lexes `message` and checks the tokens cover it contiguously
+pub fn lex(message: &str) {
+    let tokens: Vec<Token> = Lexer::new(message).collect();
+    let mut end = 0;
+    for token in tokens.iter() {
+        assert_eq!(token.get_start_index(), end);
+        assert_eq!(
+            token.as_str().len(),
+            token.get_end_index() - token.get_start_index()
+        );
+        end = token.get_end_index();
+    }
+    assert_eq!(end, message.len());
+}
+
+/// Parses a token sequence picked by `data`, including sequences the lexer never produces,
+/// like two words in a row
+pub fn parse_tokens(data: &[u8], mode: ParseMode) -> Result<(), SyntaxError> {
+    const WORDS: [&str; 6] = ["feat", "api", "Refs", "Revert", "\"x", "✨"];
+    let mut message = String::new();
+    let mut spans = Vec::with_capacity(data.len());
+    for byte in data {
+        let text = match byte % 8 {
+            0 => WORDS[(byte / 8) as usize % WORDS.len()],
+            1 => "!",
+            2 => ":",
+            3 => "#",
+            4 => "\n",
+            5 => ")",
+            6 => "(",
+            _ => " ",
+        };
+        spans.push((byte % 8, message.len()..message.len() + text.len()));
+        message.push_str(text);
+    }
+
+    let source = SlicableRcString::new(Rc::new(message));
+    let tokens: Vec<Token> = spans
+        .into_iter()
+        .map(|(kind, span)| {
+            let slice = source.substr(span);
+            match kind {
+                0 => Token::Word(WordDetails::new(slice)),
+                1 => Token::Bang(slice),
+                2 => Token::Colon(slice),
+                3 => Token::Hash(slice),
+                4 => Token::Newline(slice),
+                5 => Token::ParenthesisClose(slice),
+                6 => Token::ParenthesisOpen(slice),
+                _ => Token::Space(slice),
+            }
+        })
+        .collect();
+    let parsed = Parser::process(tokens, mode)?;
+    // Symbol accessors slice the source, they must hold for anything the parser accepts
+    for symbol in [parsed.topic, parsed.scope, parsed.description, parsed.body]
+        .into_iter()
+        .flatten()
+        .chain(parsed.footers.into_iter().flatten())
+    {
+        symbol.full_string();
+        symbol.content_string();
+        symbol.content_span();
+    }
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use std::{
+    fs::{self, create_dir_all},
+    path::{Path, PathBuf},
+};
+
+use crate::parser_lib::errors::HookError;
+
+use super::repository::git_config;
+
+const MARKER: &str = "# Installed by rustycommits";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Hook {
+    /// Lints the message of every commit
+    CommitMsg,
+    /// Lints every commit in the range being pushed
+    PrePush,
+    /// Runs `rustycommits compose` when committing without a message
+    PrepareCommitMsg,
+}
+
+impl Hook {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Hook::CommitMsg => "commit-msg",
+            Hook::PrePush => "pre-push",
+            Hook::PrepareCommitMsg => "prepare-commit-msg",
+        }
+    }
+
+    fn chained_file_name(&self) -> String {
+        format!("{}.chained", self.file_name())
+    }
+
+    fn script(&self, chain: bool) -> String {
+        let chained = format!("\"$(dirname \"$0\")/{}\"", self.chained_file_name());
+        let body = match self {
+            Hook::CommitMsg => {
+                let run_chained = if chain {
+                    format!("{} \"$@\" || exit $?\n", chained)
+                } else {
+                    String::new()
+                };
+                format!("{}exec rustycommits lint \"$1\"\n", run_chained)
+            }
+            Hook::PrePush => {
+                // Both the chained hook and the linting need the refs git writes to stdin
+                let run_chained = if chain {
+                    format!("printf '%s\\n' \"$refs\" | {} \"$@\" || exit $?\n", chained)
+                } else {
+                    String::new()
+                };
+                format!(
+                    "refs=$(cat)\n\
+                     {}\
+                     zero=$(git hash-object --stdin </dev/null | tr '0-9a-f' '0')\n\
+                     printf '%s\\n' \"$refs\" | while read -r local_ref local_sha remote_ref remote_sha; do\n\
+                     \t[ -z \"$local_sha\" ] || [ \"$local_sha\" = \"$zero\" ] && continue\n\
+                     \tif [ \"$remote_sha\" = \"$zero\" ]; then\n\
+                     \t\trange=\"$local_sha --not --remotes=$1\"\n\
+                     \telse\n\
+                     \t\trange=\"$remote_sha..$local_sha\"\n\
+                     \tfi\n\
+                     \tfor commit in $(git rev-list $range); do\n\
+                     \t\tgit log -1 --format=%B \"$commit\" | rustycommits lint || {{\n\
+                     \t\t\techo \"rustycommits: commit $commit doesn't pass linting\" >&2\n\
+                     \t\t\texit 1\n\
+                     \t\t}}\n\
+                     \tdone\n\
+                     done\n",
+                    run_chained
+                )
+            }
+            Hook::PrepareCommitMsg => {
+                let run_chained = if chain {
+                    format!("{} \"$@\" || exit $?\n", chained)
+                } else {
+                    String::new()
+                };
+                // Git doesn't connect the hook to the terminal, the prompts need it
+                format!(
+                    "{}exec < /dev/tty\nexec rustycommits compose \"$@\"\n",
+                    run_chained
+                )
+            }
+        };
+        format!("#!/bin/sh\n{}\n{}", MARKER, body)
+    }
+}
+
+/// Writes git hooks that run rustycommits into a repository's hooks directory
+pub struct HookInstaller {
+    hooks_dir: PathBuf,
+    force: bool,
+    chain: bool,
+}
+
+impl HookInstaller {
+    pub fn new(hooks_dir: PathBuf) -> Self {
+        Self {
+            hooks_dir,
+            force: false,
+            chain: false,
+        }
+    }
+
+    /// Installs into the directory set in `core.hooksPath`, or `.git/hooks` when it's not set
+    pub fn for_repository(root: &Path) -> Self {
+        let hooks_dir = match git_config(root, "core.hooksPath") {
+            Some(hooks_path) => root.join(hooks_path),
+            None => root.join(".git").join("hooks"),
+        };
+        Self::new(hooks_dir)
+    }
+
+    /// Overwrite existing hooks
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Keep existing hooks and run them before rustycommits
+    pub fn chain(mut self, chain: bool) -> Self {
+        self.chain = chain;
+        self
+    }
+
+    pub fn hooks_dir(&self) -> &Path {
+        &self.hooks_dir
+    }
+
+    pub fn install(&self, hook: Hook) -> Result<PathBuf, HookError> {
+        create_dir_all(&self.hooks_dir)?;
+        let path = self.hooks_dir.join(hook.file_name());
+        let chained_path = self.hooks_dir.join(hook.chained_file_name());
+
+        let existing = fs::read_to_string(&path).ok();
+        let is_ours = matches!(&existing, Some(content) if content.contains(MARKER));
+        let mut chain = self.chain;
+        match existing {
+            // Reinstalling keeps chaining to a hook chained earlier
+            Some(_) if is_ours => chain = chained_path.exists(),
+            Some(_) if self.chain => fs::rename(&path, &chained_path)?,
+            Some(_) if !self.force => return Err(HookError::AlreadyExists(path)),
+            _ => (),
+        }
+
+        fs::write(&path, hook.script(chain))?;
+        set_executable(&path)?;
+        Ok(path)
+    }
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o755);
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn should_install_commit_msg_hook() {
+        let dir = tempdir().unwrap();
+        let installer = HookInstaller::new(dir.path().join("hooks"));
+        let path = installer.install(Hook::CommitMsg).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("#!/bin/sh\n# Installed by rustycommits\n"));
+        assert!(content.contains("exec rustycommits lint \"$1\""));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(
+                fs::metadata(&path).unwrap().permissions().mode() & 0o111,
+                0o111
+            );
+        }
+    }
+
+    #[test]
+    fn should_not_clobber_existing_hook() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("commit-msg");
+        fs::write(&path, "#!/bin/sh\necho mine\n").unwrap();
+
+        let result = HookInstaller::new(dir.path().to_path_buf()).install(Hook::CommitMsg);
+        assert!(matches!(result, Err(HookError::AlreadyExists(_))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#!/bin/sh\necho mine\n");
+
+        HookInstaller::new(dir.path().to_path_buf())
+            .force(true)
+            .install(Hook::CommitMsg)
+            .unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains(MARKER));
+    }
+
+    #[test]
+    fn should_chain_to_existing_hook() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("pre-push");
+        fs::write(&path, "#!/bin/sh\necho mine\n").unwrap();
+
+        let installer = HookInstaller::new(dir.path().to_path_buf()).chain(true);
+        installer.install(Hook::PrePush).unwrap();
+        let chained = dir.path().join("pre-push.chained");
+        assert_eq!(
+            fs::read_to_string(chained).unwrap(),
+            "#!/bin/sh\necho mine\n"
+        );
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("| \"$(dirname \"$0\")/pre-push.chained\" \"$@\" || exit $?"));
+
+        // Reinstalling over our own hook is fine and keeps the chain
+        HookInstaller::new(dir.path().to_path_buf())
+            .install(Hook::PrePush)
+            .unwrap();
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("pre-push.chained"));
+    }
+
+    #[test]
+    fn should_use_git_hooks_dir_by_default() {
+        let dir = tempdir().unwrap();
+        let installer = HookInstaller::for_repository(dir.path());
+        assert_eq!(installer.hooks_dir(), dir.path().join(".git/hooks"));
+    }
+}
@@ -0,0 +1,97 @@
+use std::{io, path::Path, process::Command};
+
+// Fields of a commit are separated by the unit separator, commits by NUL through `-z`
+const FORMAT: &str = "--format=%H%x1f%P%x1f%an%x1f%ad%x1f%D%x1f%B";
+
+/// A commit as `git log` shows it
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct LogEntry {
+    pub hash: String,
+    /// Hashes of the parent commits, more than one for merges
+    pub parents: Vec<String>,
+    pub author: String,
+    /// Month of the author date, e.g. `2024-05`
+    pub month: String,
+    /// Tags pointing at the commit
+    pub tags: Vec<String>,
+    pub message: String,
+}
+
+/// Commits of `range` in the repository at `root`, newest first.
+/// Commits are in topological order, so merged commits come right before their merge instead of by date.
+pub fn read_log(root: &Path, range: &str) -> io::Result<Vec<LogEntry>> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "-z",
+            "--topo-order",
+            FORMAT,
+            "--date=format:%Y-%m",
+            "--decorate-refs=refs/tags/",
+            range,
+            "--",
+        ])
+        .current_dir(root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(stderr.trim().to_string()));
+    }
+    Ok(parse_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_log(output: &str) -> Vec<LogEntry> {
+    output
+        .split('\0')
+        .filter_map(|commit| {
+            let mut fields = commit.splitn(6, '\x1f');
+            let hash = fields.next()?.trim_start_matches('\n').to_string();
+            let parents = fields
+                .next()?
+                .split_whitespace()
+                .map(str::to_string)
+                .collect();
+            let author = fields.next()?.to_string();
+            let month = fields.next()?.to_string();
+            let tags = fields
+                .next()?
+                .split(", ")
+                .filter_map(|decoration| decoration.strip_prefix("tag: "))
+                .map(str::to_string)
+                .collect();
+            let message = fields.next()?.trim_end().to_string();
+            Some(LogEntry {
+                hash,
+                parents,
+                author,
+                month,
+                tags,
+                message,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_log_output() {
+        let output =
+            "a1\x1fb2 c3\x1fAda\x1f2024-05\x1ftag: v1.1, tag: latest\x1ffeat: x\n\nbody\n\0\
+                      b2\x1f\x1fBob\x1f2024-04\x1f\x1ffix: y\n\0";
+        let entries = parse_log(output);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].hash, "a1");
+        assert_eq!(entries[0].parents, ["b2", "c3"]);
+        assert_eq!(entries[0].tags, ["v1.1", "latest"]);
+        assert_eq!(entries[0].message, "feat: x\n\nbody");
+        assert_eq!(entries[1].author, "Bob");
+        assert_eq!(entries[1].month, "2024-04");
+        assert!(entries[1].tags.is_empty());
+        assert!(entries[1].parents.is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+use std::path::Path;
+
+use super::repository::git_config;
+
+const CUT_LINE: &str = "------------------------ >8 ------------------------";
+
+// Characters git picks from when `core.commentChar` is `auto`
+const AUTO_COMMENT_CHARS: &str = "#;@!$%^&|:";
+
+/// What git removes from a message before committing, see `commit.cleanup`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum CleanupMode {
+    /// Remove comment lines, everything below the scissors line and surrounding whitespace
+    Strip,
+    /// Only remove surrounding whitespace and repeated blank lines
+    Whitespace,
+    /// Like `Whitespace`, but also remove everything below the scissors line
+    Scissors,
+    /// Leave the message as it is
+    Verbatim,
+}
+
+/// Cleans up a message the way git does before committing.
+/// Hooks receive the message before this cleanup, messages of existing commits after it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageCleanup {
+    mode: CleanupMode,
+    // None for `core.commentChar = auto`
+    comment: Option<String>,
+}
+
+impl Default for MessageCleanup {
+    fn default() -> Self {
+        Self::new(CleanupMode::Strip, "#")
+    }
+}
+
+impl MessageCleanup {
+    pub fn new(mode: CleanupMode, comment: &str) -> Self {
+        Self {
+            mode,
+            comment: Some(comment.to_string()),
+        }
+    }
+
+    /// Reads `commit.cleanup` and `core.commentChar` from the repository config.
+    /// `default` is treated as `strip`, the mode git uses when the message was edited.
+    pub fn from_git_config(root: &Path) -> Self {
+        let mode = match git_config(root, "commit.cleanup").as_deref() {
+            Some("verbatim") => CleanupMode::Verbatim,
+            Some("whitespace") => CleanupMode::Whitespace,
+            Some("scissors") => CleanupMode::Scissors,
+            _ => CleanupMode::Strip,
+        };
+        let comment = git_config(root, "core.commentString")
+            .or_else(|| git_config(root, "core.commentChar"))
+            .unwrap_or_else(|| "#".to_string());
+        Self {
+            mode,
+            comment: Some(comment).filter(|comment| comment != "auto"),
+        }
+    }
+
+    pub fn apply(&self, message: &str) -> String {
+        if self.mode == CleanupMode::Verbatim {
+            return message.to_string();
+        }
+
+        let comment = self.comment_for(message);
+        let scissors = format!("{} {}", comment, CUT_LINE);
+        let cut = matches!(self.mode, CleanupMode::Strip | CleanupMode::Scissors);
+        let strip = self.mode == CleanupMode::Strip;
+
+        let mut lines: Vec<&str> = Vec::new();
+        for line in message.lines() {
+            if cut && line == scissors {
+                break;
+            }
+            if strip && line.starts_with(comment.as_str()) {
+                continue;
+            }
+            let line = line.trim_end();
+            // Git collapses runs of blank lines into one
+            if line.is_empty() && lines.last().is_some_and(|last| last.is_empty()) {
+                continue;
+            }
+            lines.push(line);
+        }
+        lines.join("\n").trim_matches('\n').to_string()
+    }
+
+    // With `auto` git picks a character no line starts with and appends its comments
+    // at the end of the template, so the last line that starts with a candidate shows it.
+    fn comment_for(&self, message: &str) -> String {
+        if let Some(comment) = &self.comment {
+            return comment.clone();
+        }
+        message
+            .lines()
+            .rev()
+            .filter_map(|line| line.chars().next())
+            .find(|first| AUTO_COMMENT_CHARS.contains(*first))
+            .unwrap_or('#')
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_remove_comments_and_trailing_whitespace() {
+        let message = "feat: add users  \n\n\nbody line\n# Please enter the commit message\n#\n\n";
+        assert_eq!(
+            MessageCleanup::default().apply(message),
+            "feat: add users\n\nbody line"
+        );
+    }
+
+    #[test]
+    fn should_drop_everything_below_scissors() {
+        let message = format!("fix: x\n# {}\ndiff --git a/x b/x\n", CUT_LINE);
+        assert_eq!(MessageCleanup::default().apply(&message), "fix: x");
+        let cleanup = MessageCleanup::new(CleanupMode::Scissors, "#");
+        assert_eq!(cleanup.apply(&message), "fix: x");
+    }
+
+    #[test]
+    fn should_use_configured_comment_char() {
+        let message = "fix: x\n\n#123 follow-up\n; Please enter the commit message\n";
+        let cleanup = MessageCleanup::new(CleanupMode::Strip, ";");
+        assert_eq!(cleanup.apply(message), "fix: x\n\n#123 follow-up");
+
+        let auto = MessageCleanup {
+            mode: CleanupMode::Strip,
+            comment: None,
+        };
+        assert_eq!(auto.apply(message), "fix: x\n\n#123 follow-up");
+    }
+
+    #[test]
+    fn should_keep_comments_unless_stripping() {
+        let message = "fix: x\n\n# not a comment  \n";
+        let cleanup = MessageCleanup::new(CleanupMode::Whitespace, "#");
+        assert_eq!(cleanup.apply(message), "fix: x\n\n# not a comment");
+        let cleanup = MessageCleanup::new(CleanupMode::Verbatim, "#");
+        assert_eq!(cleanup.apply(message), message);
+    }
+}
@@ -0,0 +1,11 @@
+mod hooks;
+mod log;
+mod message;
+mod repository;
+mod trailers;
+
+pub use self::hooks::{Hook, HookInstaller};
+pub use self::log::{read_log, LogEntry};
+pub use self::message::{CleanupMode, MessageCleanup};
+pub use self::repository::{find_git_root, is_git_root, staged_files};
+pub use self::trailers::{parse_trailers, Trailer, DEFAULT_SEPARATORS};
@@ -0,0 +1,73 @@
+use std::{
+    fs::metadata,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+pub fn is_git_root(dir: &Path) -> bool {
+    match metadata(dir.join(".git")) {
+        Ok(meta) => meta.is_dir(),
+        Err(_) => false,
+    }
+}
+
+// Start looking for the repository root in the given directory, then in parent directories
+pub fn find_git_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|ancestor| is_git_root(ancestor))
+        .map(|root| root.to_path_buf())
+}
+
+/// Reads a value from the git config of the repository, None when unset or git isn't available
+pub fn git_config(root: &Path, key: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "--get", key])
+        .current_dir(root)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8(output.stdout).ok()?;
+    Some(value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+/// Paths of the files staged for the next commit, empty when git isn't available
+pub fn staged_files(root: &Path) -> Vec<String> {
+    let output = Command::new("git")
+        .args(["diff", "--cached", "--name-only", "-z"])
+        .current_dir(root)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::create_dir_all;
+
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn should_find_git_root_from_sub_directory() {
+        let dir = tempdir().unwrap();
+        create_dir_all(dir.path().join(".git")).unwrap();
+        create_dir_all(dir.path().join("src/lib")).unwrap();
+
+        assert!(is_git_root(dir.path()));
+        assert!(!is_git_root(&dir.path().join("src")));
+        assert_eq!(
+            find_git_root(&dir.path().join("src/lib")).unwrap(),
+            dir.path()
+        );
+    }
+}
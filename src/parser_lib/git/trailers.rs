@@ -0,0 +1,266 @@
+use std::ops::Range;
+
+/// Separators git uses when `trailer.separators` isn't configured
+pub const DEFAULT_SEPARATORS: &str = ":";
+
+// Lines git itself writes, they mark a paragraph as trailers even when it has other lines too
+const GIT_GENERATED_PREFIXES: [&str; 2] = ["Signed-off-by: ", "(cherry picked from commit "];
+
+const COMMENT_CHAR: char = '#';
+
+/// A trailer as `git interpret-trailers` sees it
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Trailer {
+    pub key: String,
+    pub separator: char,
+    /// Value with folded continuation lines joined by a space, like `--unfold`
+    pub value: String,
+    pub key_span: Range<usize>,
+    /// Span of the whole trailer including its continuation lines
+    pub span: Range<usize>,
+    /// Whether the value continues on following lines
+    pub folded: bool,
+}
+
+struct Line<'a> {
+    start: usize,
+    text: &'a str,
+}
+
+fn lines(message: &str) -> Vec<Line<'_>> {
+    let mut start = 0;
+    message
+        .split_inclusive('\n')
+        .map(|text| {
+            let line = Line {
+                start,
+                text: text.trim_end_matches('\n'),
+            };
+            start += text.len();
+            line
+        })
+        .collect()
+}
+
+fn is_blank(line: &str) -> bool {
+    line.chars().all(char::is_whitespace)
+}
+
+fn starts_with_whitespace(line: &str) -> bool {
+    line.starts_with(char::is_whitespace)
+}
+
+// Position of the separator after the key, `None` when the line isn't `key<sep>value`.
+// Whitespace between the key and the separator is allowed, like `Key : value`.
+fn find_separator(line: &str, separators: &str) -> Option<usize> {
+    let mut whitespace_found = false;
+    for (i, c) in line.char_indices() {
+        if separators.contains(c) {
+            return Some(i);
+        }
+        if !whitespace_found && (c.is_ascii_alphanumeric() || c == '-') {
+            continue;
+        }
+        if i != 0 && (c == ' ' || c == '\t') {
+            whitespace_found = true;
+            continue;
+        }
+        break;
+    }
+    None
+}
+
+fn is_trailer_line(line: &str, separators: &str) -> bool {
+    matches!(find_separator(line, separators), Some(pos) if pos >= 1)
+}
+
+// Lines before a `---` patch divider and without trailing comments and blank lines
+fn end_of_log_message(lines: &[Line]) -> usize {
+    let patch_start = lines
+        .iter()
+        .position(|line| {
+            line.text.starts_with("---")
+                && line.text[3..]
+                    .chars()
+                    .next()
+                    .is_none_or(char::is_whitespace)
+        })
+        .unwrap_or(lines.len());
+    lines[..patch_start]
+        .iter()
+        .rposition(|line| !is_blank(line.text) && !line.text.starts_with(COMMENT_CHAR))
+        .map_or(0, |i| i + 1)
+}
+
+// Index of the first line of the trailer block, mirrors `find_trailer_block_start` in git's trailer.c
+fn trailer_block_start(lines: &[Line], separators: &str) -> Option<usize> {
+    // The first paragraph is the title and can't be trailers
+    let end_of_title = lines
+        .iter()
+        .position(|line| !line.text.starts_with(COMMENT_CHAR) && is_blank(line.text))
+        .unwrap_or(lines.len());
+
+    let mut recognized_prefix = false;
+    let mut trailer_lines = 0;
+    let mut non_trailer_lines = 0;
+    // Continuation lines belong to the trailer above them, or are non-trailers if there's none
+    let mut possible_continuation_lines = 0;
+
+    for i in (end_of_title..lines.len()).rev() {
+        let line = lines[i].text;
+        if line.starts_with(COMMENT_CHAR) {
+            non_trailer_lines += possible_continuation_lines;
+            possible_continuation_lines = 0;
+            continue;
+        }
+        if is_blank(line) {
+            non_trailer_lines += possible_continuation_lines;
+            let is_block = (recognized_prefix && trailer_lines * 3 >= non_trailer_lines)
+                || (trailer_lines > 0 && non_trailer_lines == 0);
+            return is_block.then_some(i + 1);
+        }
+
+        if GIT_GENERATED_PREFIXES
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+        {
+            trailer_lines += 1;
+            possible_continuation_lines = 0;
+            recognized_prefix = true;
+        } else if is_trailer_line(line, separators) && !starts_with_whitespace(line) {
+            trailer_lines += 1;
+            possible_continuation_lines = 0;
+        } else if starts_with_whitespace(line) {
+            possible_continuation_lines += 1;
+        } else {
+            non_trailer_lines += 1 + possible_continuation_lines;
+            possible_continuation_lines = 0;
+        }
+    }
+    None
+}
+
+// Joins continuation lines with a single space, like `unfold_value` in git
+fn unfold(value: &str) -> String {
+    let mut unfolded = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            unfolded.push(' ');
+        } else {
+            unfolded.push(c);
+        }
+    }
+    unfolded.trim().to_string()
+}
+
+/// Parses the trailers of a message the way `git interpret-trailers --parse` does.
+/// `separators` are the characters that can follow a key, like git's `trailer.separators`.
+///
+/// ```
+/// use parser_lib::git::parse_trailers;
+///
+/// let trailers = parse_trailers("fix: x\n\nAcked-by: A\nRefs: #1\n", ":");
+/// assert_eq!(trailers[0].key, "Acked-by");
+/// assert_eq!(trailers[1].value, "#1");
+/// ```
+pub fn parse_trailers(message: &str, separators: &str) -> Vec<Trailer> {
+    let lines = lines(message);
+    let end = end_of_log_message(&lines);
+    let Some(start) = trailer_block_start(&lines[..end], separators) else {
+        return Vec::new();
+    };
+
+    let mut trailers: Vec<Trailer> = Vec::new();
+    // Continuation lines only extend a trailer when it's the line right above them
+    let mut extends_last = false;
+    for line in lines[start..end].iter() {
+        if extends_last && starts_with_whitespace(line.text) {
+            let last = trailers.last_mut().unwrap();
+            last.value = format!("{}\n{}", last.value, line.text);
+            last.span.end = line.start + line.text.len();
+            last.folded = true;
+            continue;
+        }
+
+        extends_last = false;
+        if line.text.starts_with(COMMENT_CHAR) {
+            continue;
+        }
+        if let Some(separator) = find_separator(line.text, separators).filter(|&pos| pos >= 1) {
+            let key = line.text[..separator].trim_end();
+            let separator_char = line.text[separator..].chars().next().unwrap();
+            trailers.push(Trailer {
+                key: key.to_string(),
+                separator: separator_char,
+                value: line.text[separator + separator_char.len_utf8()..].to_string(),
+                key_span: line.start..line.start + key.len(),
+                span: line.start..line.start + line.text.len(),
+                folded: false,
+            });
+            extends_last = true;
+        }
+    }
+
+    for trailer in trailers.iter_mut() {
+        trailer.value = unfold(&trailer.value);
+    }
+    trailers
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::*;
+
+    // Formats trailers like `git interpret-trailers --parse`
+    fn format_parsed(trailers: &[Trailer], separators: &str) -> String {
+        let separator = separators.chars().next().unwrap();
+        trailers
+            .iter()
+            .map(|trailer| format!("{}{} {}\n", trailer.key, separator, trailer.value))
+            .collect()
+    }
+
+    // Each fixture is a message and the output of `git interpret-trailers --parse` for it,
+    // see generate.sh in the fixture directory
+    #[test]
+    fn should_match_git_interpret_trailers() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/trailers");
+        let mut checked = 0;
+        for entry in fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("msg") {
+                continue;
+            }
+            let message = fs::read_to_string(&path).unwrap();
+            for (separators, extension) in [(DEFAULT_SEPARATORS, "git"), (":#", "hash.git")] {
+                let expected = fs::read_to_string(path.with_extension(extension)).unwrap();
+                let trailers = parse_trailers(&message, separators);
+                assert_eq!(
+                    format_parsed(&trailers, separators),
+                    expected,
+                    "{:?} with separators '{}'",
+                    path,
+                    separators
+                );
+            }
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn should_keep_spans_of_folded_trailers() {
+        let message = "feat: x\n\nNote: first\n  second\nCo-authored-by: A <a@example.com>\n";
+        let trailers = parse_trailers(message, DEFAULT_SEPARATORS);
+        assert_eq!(&message[trailers[0].span.clone()], "Note: first\n  second");
+        assert_eq!(&message[trailers[1].key_span.clone()], "Co-authored-by");
+        assert!(trailers[0].folded);
+        assert_eq!(trailers[0].value, "first second");
+        assert!(!trailers[1].folded);
+    }
+}
@@ -4,159 +4,144 @@ use crate::parser_lib::SlicableRcString;
 
 use super::types::{Token, WordDetails};
 
-pub struct Lexer {
-    tokens: Vec<Token>,
-    word_length: usize,
-    message: SlicableRcString,
+/// Tokenizes a message lazily, the parser pulls tokens as it needs them
+pub(crate) struct Lexer<'a> {
+    message: SlicableRcString<'a>,
+    position: usize,
 }
 
 /*
  * Meaningful symbols:
  * - Bang: '!'
  * - Colon: ':'
- * - Dash: '-'
  * - Hash: '#'
  * - NewLine: '\n'
  * - Space: ' '
  * - CloseParenthesis: ')'
  * - OpenParenthesis: '('
+ * Everything between them is a word.
  */
-impl Lexer {
-    pub fn new() -> Lexer {
+fn is_delimiter(c: char) -> bool {
+    matches!(c, '!' | ':' | '#' | '\n' | ')' | '(' | ' ')
+}
+
+impl<'a> Lexer<'a> {
+    /// Tokens borrow `message`
+    pub fn new(message: &'a str) -> Lexer<'a> {
         Lexer {
-            tokens: Vec::new(),
-            word_length: 0,
-            // TODO: Come up with a better way to initialize this
-            message: SlicableRcString::new(Rc::new("empty".to_string())),
+            message: SlicableRcString::borrowed(message),
+            position: 0,
         }
     }
 
-    pub fn process(&mut self, message: &Rc<String>) -> Vec<Token> {
-        self.message = SlicableRcString::new(Rc::clone(message));
-        let char_indecies = message.char_indices().peekable();
-        for (i, c) in char_indecies {
-            match c {
-                '!' => self.push_bang(i),
-                ':' => self.push_colon(i),
-                // TODO: Come up with a better way to do these handle_x functions
-                // currently we're checking the last token pushed to see if we need to merge...
-                // Peeking would be a better option but haven't yet had the time to look into it
-                '#' => self.push_hash(i),
-                '\n' => self.push_newline(i),
-                ')' => self.push_parenthesis_close(i),
-                '(' => self.push_parenthesis_open(i),
-                ' ' => self.push_space(i),
-                _ => self.word_length += 1,
-            };
+    /// Tokens share ownership of `message` and can outlive the lexer
+    pub fn shared(message: Rc<String>) -> Lexer<'static> {
+        Lexer {
+            message: SlicableRcString::new(message),
+            position: 0,
         }
-
-        self.push_if_word(self.message.len());
-
-        let result = self.tokens.clone();
-        self.reset();
-        result
-    }
-
-    fn push_colon(&mut self, index: usize) {
-        self.push_if_word(index);
-        self.push_token(Token::Colon(self.message.substr(index..index + 1)), index)
-    }
-
-    fn push_newline(&mut self, index: usize) {
-        self.push_if_word(index);
-        self.push_token(Token::Newline(self.message.substr(index..index + 1)), index);
-    }
-
-    fn push_bang(&mut self, index: usize) {
-        self.push_token(Token::Bang(self.message.substr(index..index + 1)), index);
-    }
-
-    fn push_parenthesis_close(&mut self, index: usize) {
-        self.push_token(
-            Token::ParenthesisClose(self.message.substr(index..index + 1)),
-            index,
-        );
-    }
-
-    fn push_parenthesis_open(&mut self, index: usize) {
-        self.push_token(
-            Token::ParenthesisOpen(self.message.substr(index..index + 1)),
-            index,
-        );
     }
+}
 
-    fn push_space(&mut self, index: usize) {
-        self.push_if_word(index);
-        self.push_token(Token::Space(self.message.substr(index..index + 1)), index);
-    }
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let start = self.position;
+        let rest = &self.message[start..];
+        let first = rest.chars().next()?;
+        if is_delimiter(first) {
+            self.position += 1;
+            let value = self.message.substr(start..self.position);
+            return Some(match first {
+                '!' => Token::Bang(value),
+                ':' => Token::Colon(value),
+                '#' => Token::Hash(value),
+                '\n' => Token::Newline(value),
+                ')' => Token::ParenthesisClose(value),
+                '(' => Token::ParenthesisOpen(value),
+                _ => Token::Space(value),
+            });
+        }
 
-    fn push_hash(&mut self, index: usize) {
-        self.push_if_word(index);
-        self.push_token(Token::Hash(self.message.substr(index..index + 1)), index)
+        // Word lengths are in bytes like the indices they're sliced with
+        let length = rest.find(is_delimiter).unwrap_or(rest.len());
+        self.position += length;
+        Some(Token::Word(WordDetails::new(
+            self.message.substr(start..self.position),
+        )))
     }
+}
 
-    fn push_token(&mut self, token: Token, index: usize) {
-        self.push_if_word(index);
-        self.tokens.push(token);
-    }
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
 
-    fn push_if_word(&mut self, index: usize) {
-        if self.word_length != 0 {
-            self.push_word(index);
+    use super::*;
+    use crate::parser_lib::test_utils::arb_message;
+
+    proptest! {
+        #[test]
+        fn tokens_should_cover_message_contiguously(message in arb_message()) {
+            let tokens = Lexer::new(&message).collect::<Vec<_>>();
+            let mut end = 0;
+            for token in tokens.iter() {
+                prop_assert_eq!(token.get_start_index(), end);
+                prop_assert!(token.get_end_index() <= message.len());
+                prop_assert_eq!(token.as_str().len(), token.get_end_index() - token.get_start_index());
+                prop_assert_eq!(token.get_value(), &message[token.get_start_index()..token.get_end_index()]);
+                end = token.get_end_index();
+            }
+            prop_assert_eq!(end, message.len());
         }
-    }
 
-    fn push_word(&mut self, end_i: usize) {
-        let start_i = end_i - self.word_length;
-        let string: SlicableRcString = self.message.substr(start_i..end_i);
-        self.tokens.push(Token::Word(WordDetails::new(string)));
-        self.word_length = 0;
+        #[test]
+        fn tokens_should_concatenate_to_message(message in arb_message()) {
+            let tokens = Lexer::new(&message).collect::<Vec<_>>();
+            let joined: String = tokens.iter().map(|token| token.get_value()).collect();
+            prop_assert_eq!(joined, message);
+        }
     }
 
-    fn reset(&mut self) {
-        self.tokens.clear();
-        self.word_length = 0;
+    #[test]
+    fn should_measure_newline_tokens() {
+        let tokens = Lexer::new("a\nb\n").collect::<Vec<_>>();
+        assert!(tokens.iter().all(|token| token.as_str().len() == 1));
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
     fn should_return_word_with_correct_indecies() {
         let message = Rc::new("test".to_string());
-        // closure to make all variables to out of scope -> references to message should be dropped
-        let mut lexer = Lexer::new();
-        let tokens = lexer.process(&message);
-
-        if let Some(token) = tokens.get(0) {
-            match token {
-                Token::Word(data) => {
-                    assert_eq!(data.value(), "test".to_string());
-                    assert_eq!(data.start_index(), 0);
-                    assert_eq!(data.end_index(), 4);
-                    assert_eq!(data.len(), 4);
-                }
-                _ => {}
-            }
+        // Shared tokens keep the message alive on their own
+        let tokens: Vec<Token> = Lexer::shared(Rc::clone(&message)).collect();
+        drop(message);
+
+        if let Some(Token::Word(data)) = tokens.first() {
+            assert_eq!(data.value(), "test".to_string());
+            assert_eq!(data.start_index(), 0);
+            assert_eq!(data.end_index(), 4);
+            assert_eq!(data.len(), 4);
         }
     }
 
+    #[test]
+    fn should_slice_multi_byte_words() {
+        let tokens: Vec<Token> = Lexer::new("✨ café").collect();
+        let values: Vec<String> = tokens.iter().map(|token| token.get_value()).collect();
+        assert_eq!(values, ["✨", " ", "café"]);
+        assert_eq!(tokens[2].get_start_index(), 4);
+    }
+
     #[test]
     fn should_tokenize_simple_string() {
-        let message = Rc::new("feat: test".to_string());
-        let mut lexer = Lexer::new();
-        let tokens = lexer.process(&message);
+        let tokens: Vec<Token> = Lexer::new("feat: test").collect();
         assert_eq!(tokens.len(), 4);
 
-        match &tokens[1] {
-            Token::Colon(d) => assert_eq!(d.value(), ":"),
-            _ => {}
+        if let Token::Colon(d) = &tokens[1] {
+            assert_eq!(d.value(), ":");
         }
-        match &tokens[1] {
-            Token::Space(d) => assert_eq!(d.value(), " "),
-            _ => {}
+        if let Token::Space(d) = &tokens[2] {
+            assert_eq!(d.value(), " ");
         }
     }
 }
@@ -1,3 +1,3 @@
 mod lexer;
 pub mod types;
-pub use lexer::Lexer;
+pub(crate) use lexer::Lexer;
@@ -1,3 +1,3 @@
 mod token;
 
-pub use self::token::{Token, WordDetails};
+pub(crate) use self::token::{Token, WordDetails};
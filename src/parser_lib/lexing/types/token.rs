@@ -7,12 +7,12 @@ use crate::parser_lib::SlicableRcString;
 
 #[derive(Debug, Clone, PartialEq)]
 
-pub struct WordDetails {
-    slicabe_rc_string: SlicableRcString,
+pub(crate) struct WordDetails<'a> {
+    slicabe_rc_string: SlicableRcString<'a>,
 }
 
-impl WordDetails {
-    pub fn new(value: SlicableRcString) -> WordDetails {
+impl<'a> WordDetails<'a> {
+    pub fn new(value: SlicableRcString<'a>) -> WordDetails<'a> {
         WordDetails {
             slicabe_rc_string: value,
         }
@@ -36,19 +36,20 @@ impl WordDetails {
 }
 
 #[derive(Clone, PartialEq)]
-pub enum Token {
-    Word(WordDetails),
-    Bang(SlicableRcString),
-    Colon(SlicableRcString),
-    Hash(SlicableRcString),
-    Newline(SlicableRcString),
-    ParenthesisClose(SlicableRcString),
-    ParenthesisOpen(SlicableRcString),
-    Space(SlicableRcString),
+#[non_exhaustive]
+pub(crate) enum Token<'a> {
+    Word(WordDetails<'a>),
+    Bang(SlicableRcString<'a>),
+    Colon(SlicableRcString<'a>),
+    Hash(SlicableRcString<'a>),
+    Newline(SlicableRcString<'a>),
+    ParenthesisClose(SlicableRcString<'a>),
+    ParenthesisOpen(SlicableRcString<'a>),
+    Space(SlicableRcString<'a>),
 }
 
-impl Token {
-    fn get_srcs_ref(&self) -> &SlicableRcString {
+impl<'a> Token<'a> {
+    fn get_srcs_ref(&self) -> &SlicableRcString<'a> {
         match self {
             Token::Word(token_data) => &token_data.slicabe_rc_string,
             Token::Bang(value) => value,
@@ -61,6 +62,11 @@ impl Token {
         }
     }
 
+    /// The token's text without allocating, unlike `get_value`
+    pub fn as_str(&self) -> &str {
+        self.get_srcs_ref().as_str()
+    }
+
     pub fn get_value(&self) -> String {
         match self {
             Token::Word(token_data) => token_data.value(),
@@ -120,25 +126,33 @@ impl Token {
         }
     }
 
-    pub fn len(&self) -> usize {
-        match self {
-            Token::Word(token_data) => token_data.len(),
-            Token::Bang(value) => value.len(),
-            Token::Colon(value) => value.len(),
-            Token::Hash(value) => value.len(),
-            Token::Newline(value) => value.end_index(),
-            Token::ParenthesisClose(value) => value.len(),
-            Token::ParenthesisOpen(value) => value.len(),
-            Token::Space(value) => value.len(),
-        }
+    /// The whole message the token is part of
+    pub fn get_source(&self) -> &str {
+        self.get_srcs_ref().source()
     }
 
-    pub fn get_super_slice(&self, span: Range<usize>) -> SlicableRcString {
+    pub fn get_super_slice(&self, span: Range<usize>) -> SlicableRcString<'a> {
         self.get_srcs_ref().super_slice(span)
     }
+
+    /// Copies a borrowed message so the token can outlive it
+    pub fn into_owned(self) -> Token<'static> {
+        match self {
+            Token::Word(token_data) => {
+                Token::Word(WordDetails::new(token_data.slicabe_rc_string.into_owned()))
+            }
+            Token::Bang(value) => Token::Bang(value.into_owned()),
+            Token::Colon(value) => Token::Colon(value.into_owned()),
+            Token::Hash(value) => Token::Hash(value.into_owned()),
+            Token::Newline(value) => Token::Newline(value.into_owned()),
+            Token::ParenthesisClose(value) => Token::ParenthesisClose(value.into_owned()),
+            Token::ParenthesisOpen(value) => Token::ParenthesisOpen(value.into_owned()),
+            Token::Space(value) => Token::Space(value.into_owned()),
+        }
+    }
 }
 
-impl Debug for Token {
+impl Debug for Token<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if f.alternate() {
             write!(
@@ -153,7 +167,7 @@ impl Debug for Token {
     }
 }
 
-impl Display for Token {
+impl Display for Token<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if f.alternate() {
             write!(
@@ -167,9 +181,3 @@ impl Display for Token {
         }
     }
 }
-
-impl ToString for Token {
-    fn to_string(&self) -> String {
-        self.get_value()
-    }
-}
@@ -1,21 +1,112 @@
+mod compose;
 mod errors;
+#[cfg(fuzzing)]
+pub mod fuzzing;
+mod git;
 mod lexing;
 mod parsing;
 mod slicable_rc_string;
+mod stats;
+mod text_length;
 mod validation;
 
-pub use slicable_rc_string::SlicableRcString;
-use std::rc::Rc;
-
-use self::{
-    errors::SyntaxError,
-    lexing::Lexer,
-    parsing::{types::CommitMessage, Parser},
-    validation::{load_rules, Validator},
+pub use compose::Composer;
+pub use errors::{HookError, SyntaxError, UnexpectedToken, ValidationError};
+pub use git::{
+    find_git_root, parse_trailers, read_log, staged_files, CleanupMode, Hook, HookInstaller,
+    LogEntry, MessageCleanup, Trailer,
+};
+pub use parsing::types::{
+    cancel_reverts, CommitMessage, IssueReference, ParseMode, Revert, StyleDiagnostic, StyleIssue,
+    Symbol, TextCase,
 };
+pub(crate) use slicable_rc_string::SlicableRcString;
+pub use stats::{Conformance, Release, Stats};
+use std::{ops::Range, rc::Rc};
+pub use text_length::{LengthUnit, Position};
+pub use validation::rules::{
+    AllowedEntry, AllowedList, DescriptionRules, Preset, ScopeRules, TopicRules, ValidationRules,
+};
+pub use validation::{apply_fixes, load_rules, Fix, Validator};
+
+use self::{lexing::Lexer, parsing::Parser};
 
+/// Lexes and parses commit messages, and validates them against rules configured once
+///
+/// ```
+/// use parser_lib::{CommitMessageParser, ParseMode, StyleIssue};
+///
+/// let mut parser = CommitMessageParser::builder()
+///     .parse_mode(ParseMode::Lenient)
+///     .build();
+/// let (message, errors) = parser
+///     .process("feat (api): add users".to_string())
+///     .unwrap()
+///     .checked()
+///     .unwrap();
+/// assert_eq!(message.scope().unwrap().content_string(), "api");
+/// assert_eq!(message.diagnostics()[0].issue(), StyleIssue::SpaceBeforeScope);
+/// // Only a warning unless `style_errors` is set
+/// assert!(errors.is_empty());
+/// assert_eq!(parser.rules().style_warnings(&message).len(), 1);
+///
+/// let mut strict = CommitMessageParser::new();
+/// assert!(strict.process("feat (api): add users".to_string()).is_err());
+/// ```
 pub struct CommitMessageParser {
-    lexer: Lexer,
+    mode: ParseMode,
+    rules: ValidationRules,
+}
+
+/// What `CommitMessageParser::process` made of a message
+// Returned once per message and not stored, so boxing the message would only add an allocation
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Processed<'a> {
+    /// Parsed and validated against the rules
+    Checked(CommitMessage<'a>, Vec<ValidationError>),
+    /// Skipped by the ignore rules, e.g. a merge or `fixup!` commit
+    Ignored,
+}
+
+impl<'a> Processed<'a> {
+    /// The parsed message and its validation errors, `None` when it was ignored
+    pub fn checked(self) -> Option<(CommitMessage<'a>, Vec<ValidationError>)> {
+        match self {
+            Processed::Checked(message, errors) => Some((message, errors)),
+            Processed::Ignored => None,
+        }
+    }
+}
+
+/// Configures a `CommitMessageParser`, made by `CommitMessageParser::builder`
+#[derive(Default)]
+pub struct CommitMessageParserBuilder {
+    mode: Option<ParseMode>,
+    rules: Option<ValidationRules>,
+}
+
+impl CommitMessageParserBuilder {
+    /// Rules to validate with, the default preset when not set
+    pub fn rules(mut self, rules: ValidationRules) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Overrides the parse mode of the rules
+    pub fn parse_mode(mut self, mode: ParseMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    pub fn build(self) -> CommitMessageParser {
+        let rules = self.rules.unwrap_or_default();
+        CommitMessageParser {
+            mode: self.mode.unwrap_or(rules.parse_mode()),
+            rules,
+        }
+    }
 }
 
 impl Default for CommitMessageParser {
@@ -26,20 +117,188 @@ impl Default for CommitMessageParser {
 
 impl CommitMessageParser {
     pub fn new() -> Self {
-        Self {
-            lexer: Lexer::new(),
-        }
+        Self::builder().build()
+    }
+
+    pub fn with_mode(mode: ParseMode) -> Self {
+        Self::builder().parse_mode(mode).build()
     }
 
-    pub fn process(&mut self, commit_message: String) -> Result<CommitMessage, SyntaxError> {
-        let message = Rc::new(commit_message);
-        let tokens = self.lexer.process(&message);
-        let parsed = Parser::process(tokens)?;
-        let _validation_result = Validator::validate(&parsed, load_rules());
+    pub fn builder() -> CommitMessageParserBuilder {
+        CommitMessageParserBuilder::default()
+    }
 
-        return Ok(parsed);
+    pub fn rules(&self) -> &ValidationRules {
+        &self.rules
+    }
+
+    /// Parses the message and validates it against the parser's rules.
+    /// Messages the ignore rules skip, e.g. merge commits, aren't parsed.
+    pub fn process(&mut self, commit_message: String) -> Result<Processed<'static>, SyntaxError> {
+        let Some(span) = self.rules.lint_span(&commit_message) else {
+            return Ok(Processed::Ignored);
+        };
+        let tokens = Lexer::shared(Rc::new(commit_message));
+        self.check_tokens(tokens, span)
+    }
+
+    /// Like `process`, but without copying the message
+    ///
+    /// ```
+    /// use parser_lib::{CommitMessageParser, Processed};
+    ///
+    /// let mut parser = CommitMessageParser::new();
+    /// assert!(matches!(parser.check("Merge branch 'main'"), Ok(Processed::Ignored)));
+    ///
+    /// let (message, errors) = parser.check("feat: add users").unwrap().checked().unwrap();
+    /// assert_eq!(message.topic().unwrap().content_str(), "feat");
+    /// assert!(errors.is_empty());
+    /// ```
+    pub fn check<'a>(&mut self, commit_message: &'a str) -> Result<Processed<'a>, SyntaxError> {
+        let Some(span) = self.rules.lint_span(commit_message) else {
+            return Ok(Processed::Ignored);
+        };
+        self.check_tokens(Lexer::new(commit_message), span)
+    }
+
+    // Parses the tokens inside `span`, e.g. only the target header of a `fixup!` commit.
+    // Spans in the result still point into the whole message.
+    fn check_tokens<'a>(
+        &mut self,
+        tokens: Lexer<'a>,
+        span: Range<usize>,
+    ) -> Result<Processed<'a>, SyntaxError> {
+        let start = span.start;
+        let tokens = tokens
+            .skip_while(move |token| token.get_start_index() < start)
+            .take_while(move |token| token.get_end_index() <= span.end);
+        let parsed = Parser::process(tokens, self.mode)
+            .map_err(|err| err.in_unit(self.rules.length_unit()))?;
+        let errors = Validator::validate(&parsed, &self.rules);
+        Ok(Processed::Checked(parsed, errors))
+    }
+
+    /// Parses without copying the message, symbols borrow from it
+    ///
+    /// ```
+    /// use parser_lib::CommitMessageParser;
+    ///
+    /// let text = String::from("fix(lexer): slice multi-byte words");
+    /// let message = CommitMessageParser::new().parse(&text).unwrap();
+    /// assert_eq!(message.scope().unwrap().content_str(), "lexer");
+    /// ```
+    pub fn parse<'a>(&mut self, commit_message: &'a str) -> Result<CommitMessage<'a>, SyntaxError> {
+        Parser::process(Lexer::new(commit_message), self.mode)
+            .map_err(|err| err.in_unit(self.rules.length_unit()))
+    }
+
+    /// Like `parse`, but takes the message so the symbols can outlive it.
+    /// Unlike `process`, the message is neither validated nor checked against the ignore rules.
+    ///
+    /// ```
+    /// use parser_lib::CommitMessageParser;
+    ///
+    /// let message = CommitMessageParser::new()
+    ///     .parse_owned(String::from("Merge: not a conventional commit"))
+    ///     .unwrap();
+    /// assert_eq!(message.topic().unwrap().content_string(), "Merge");
+    /// ```
+    pub fn parse_owned(
+        &mut self,
+        commit_message: String,
+    ) -> Result<CommitMessage<'static>, SyntaxError> {
+        Parser::process(Lexer::shared(Rc::new(commit_message)), self.mode)
+            .map_err(|err| err.in_unit(self.rules.length_unit()))
+    }
+
+    /// Parses the first line only, for when scanning history needs just the topic, scope and
+    /// breaking flag. The body and footers are left unparsed, and not even lexed. The lines
+    /// are only scanned for a `BREAKING CHANGE: ` footer.
+    ///
+    /// ```
+    /// use parser_lib::CommitMessageParser;
+    ///
+    /// let message = CommitMessageParser::new()
+    ///     .parse_header("feat(api)!: drop v1\n\nLong body...")
+    ///     .unwrap();
+    /// assert_eq!(message.scope().unwrap().content_str(), "api");
+    /// assert!(message.is_breaking());
+    /// assert!(message.body().is_none());
+    ///
+    /// let message = CommitMessageParser::new()
+    ///     .parse_header("feat: x\n\nBREAKING CHANGE: gone")
+    ///     .unwrap();
+    /// assert!(message.is_breaking());
+    /// ```
+    pub fn parse_header<'a>(
+        &mut self,
+        commit_message: &'a str,
+    ) -> Result<CommitMessage<'a>, SyntaxError> {
+        let mut message = Parser::process_header(Lexer::new(commit_message), self.mode)
+            .map_err(|err| err.in_unit(self.rules.length_unit()))?;
+        message.scan_breaking_footer(commit_message);
+        Ok(message)
     }
 }
 
 #[cfg(test)]
 pub mod test_utils;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser_lib::test_utils::rules_from;
+
+    #[test]
+    fn should_not_parse_ignored_messages() {
+        let mut parser = CommitMessageParser::new();
+        let processed = parser.process("Merge branch 'main'".to_string()).unwrap();
+        assert!(matches!(processed, Processed::Ignored));
+    }
+
+    #[test]
+    fn should_only_scan_last_paragraph_for_breaking_footer() {
+        let mut parser = CommitMessageParser::new();
+        let message = "feat: x\n\nBREAKING CHANGE: reads like a footer\n\nbut this is the body.";
+        assert!(!parser.parse_header(message).unwrap().is_breaking());
+        let message = "feat: x\n\nsome body\n\nRefs: #12\nBREAKING-CHANGE: gone\n\n";
+        assert!(parser.parse_header(message).unwrap().is_breaking());
+        assert!(!parser
+            .parse_header("feat: x\nBREAKING CHANGE: gone")
+            .unwrap()
+            .is_breaking());
+    }
+
+    #[test]
+    fn should_report_style_as_warnings_unless_style_errors_is_set() {
+        let mut parser = CommitMessageParser::with_mode(ParseMode::Lenient);
+        let (message, errors) = parser.check("feat (api): x").unwrap().checked().unwrap();
+        assert!(errors.is_empty());
+        let warnings = parser.rules().style_warnings(&message);
+        assert!(matches!(
+            &warnings[..],
+            [ValidationError::StyleWarning { issue: StyleIssue::SpaceBeforeScope, span }]
+                if *span == (4..5)
+        ));
+
+        let mut parser = CommitMessageParser::builder()
+            .rules(rules_from("parse_mode = 'lenient'\nstyle_errors = true"))
+            .build();
+        let (message, errors) = parser.check("feat (api): x").unwrap().checked().unwrap();
+        assert!(matches!(&errors[..], [ValidationError::Style { .. }]));
+        assert!(parser.rules().style_warnings(&message).is_empty());
+    }
+
+    #[test]
+    fn should_check_fixup_target_in_place() {
+        let mut parser = CommitMessageParser::builder()
+            .rules(rules_from("ignore.validate_fixup_target = true"))
+            .build();
+
+        let message = "fixup! fature: add users\n\nnot linted";
+        let (parsed, errors) = parser.check(message).unwrap().checked().unwrap();
+        assert_eq!(parsed.header(), "fature: add users");
+        assert!(parsed.body().is_none());
+        assert_eq!(&message[errors[0].span().unwrap()], "fature");
+    }
+}
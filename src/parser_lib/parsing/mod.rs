@@ -3,3 +3,4 @@ mod parser_functions;
 pub mod types;
 
 pub use parser::Parser;
+pub use parser_functions::determine_text_case;
@@ -1,39 +1,207 @@
+use std::{collections::VecDeque, iter::Fuse};
+
 use itertools::Itertools;
 
 use crate::parser_lib::{errors::SyntaxError, lexing::types::Token};
 
 use super::{
-    parser_functions::{parse_body, parse_description, parse_footers, parse_scope, parse_topic},
-    types::CommitMessage,
+    parser_functions::{
+        is_git_revert_header, parse_body, parse_description, parse_footers, parse_gitmoji_topic,
+        parse_scope, parse_spaced_description, parse_topic,
+    },
+    types::{CommitMessage, Diagnostics, ParseMode, StyleIssue, Symbol, TokenIter},
 };
 
 pub struct Parser {}
 impl Parser {
-    pub fn process(tokens: Vec<Token>) -> Result<CommitMessage, SyntaxError> {
-        let mut tokens_iter = tokens.into_iter().multipeek();
-        let topic = parse_topic(&mut tokens_iter)?;
-        let scope = parse_scope(&mut tokens_iter)?;
-        let description = parse_description(&mut tokens_iter)?;
+    pub fn process<'a, I>(tokens: I, mode: ParseMode) -> Result<CommitMessage<'a>, SyntaxError>
+    where
+        I: IntoIterator<Item = Token<'a>>,
+        I::IntoIter: 'a,
+    {
+        let mut tokens_iter = Self::token_iter(TrimTrailingNewlines::new(tokens));
+        let mut diagnostics = Diagnostics::new(mode);
+        let (topic, scope, description) = parse_header(&mut tokens_iter, &mut diagnostics)?;
         let body = parse_body(&mut tokens_iter)?;
         let footers = parse_footers(&mut tokens_iter)?;
 
-        // At this point, I'm happy to have used Rc<string>, but now I just want to move onwards.
-        // I'll just clone the strings and move on. Maybe I'll switch to RC<string> later on
-
         Ok(CommitMessage {
             topic: Some(topic),
             scope,
             description: Some(description),
             body,
             footers,
+            diagnostics: diagnostics.into_vec(),
+            breaking_footer: false,
+        })
+    }
+
+    /// Parses the header only, tokens after its end are never pulled
+    pub fn process_header<'a, I>(
+        tokens: I,
+        mode: ParseMode,
+    ) -> Result<CommitMessage<'a>, SyntaxError>
+    where
+        I: IntoIterator<Item = Token<'a>>,
+        I::IntoIter: 'a,
+    {
+        let mut diagnostics = Diagnostics::new(mode);
+        let (topic, scope, description) =
+            parse_header(&mut Self::token_iter(tokens), &mut diagnostics)?;
+
+        Ok(CommitMessage {
+            topic: Some(topic),
+            scope,
+            description: Some(description),
+            body: None,
+            footers: None,
+            diagnostics: diagnostics.into_vec(),
+            breaking_footer: false,
         })
     }
+
+    fn token_iter<'a, I>(tokens: I) -> TokenIter<'a>
+    where
+        I: IntoIterator<Item = Token<'a>>,
+        I::IntoIter: 'a,
+    {
+        let tokens: Box<dyn Iterator<Item = Token<'a>> + 'a> = Box::new(tokens.into_iter());
+        tokens.multipeek()
+    }
+}
+
+/// Drops the newlines the message ends with, as in `fix: x\n`. They aren't part of any symbol.
+/// Only runs of newlines are buffered, so tokens are still pulled one line at a time.
+struct TrimTrailingNewlines<'a, I: Iterator<Item = Token<'a>>> {
+    tokens: Fuse<I>,
+    // Newlines that may end the message and the token after them
+    buffered: VecDeque<Token<'a>>,
+}
+
+impl<'a, I: Iterator<Item = Token<'a>>> TrimTrailingNewlines<'a, I> {
+    fn new(tokens: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            tokens: tokens.into_iter().fuse(),
+            buffered: VecDeque::new(),
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = Token<'a>>> Iterator for TrimTrailingNewlines<'a, I> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        if let Some(token) = self.buffered.pop_front() {
+            return Some(token);
+        }
+        let token = self.tokens.next()?;
+        if !matches!(token, Token::Newline(_)) {
+            return Some(token);
+        }
+
+        self.buffered.push_back(token);
+        loop {
+            match self.tokens.next() {
+                Some(token @ Token::Newline(_)) => self.buffered.push_back(token),
+                Some(token) => {
+                    self.buffered.push_back(token);
+                    return self.buffered.pop_front();
+                }
+                None => {
+                    self.buffered.clear();
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+fn parse_header<'a>(
+    tokens: &mut TokenIter<'a>,
+    diagnostics: &mut Diagnostics,
+) -> Result<(Symbol<'a>, Option<Symbol<'a>>, Symbol<'a>), SyntaxError> {
+    diagnostics.skip(tokens, StyleIssue::WhitespaceBeforeHeader, |token| {
+        matches!(token, Token::Space(_) | Token::Newline(_))
+    });
+
+    match parse_gitmoji_topic(tokens) {
+        Some(topic) => Ok((topic, None, parse_spaced_description(tokens)?)),
+        None => {
+            let topic = parse_topic(tokens)?;
+            if is_git_revert_header(&topic, tokens) {
+                Ok((topic, None, parse_spaced_description(tokens)?))
+            } else {
+                let scope = parse_scope(tokens, diagnostics)?;
+                let description = parse_description(tokens, diagnostics)?;
+                Ok((topic, scope, description))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{cell::Cell, ops::Range, rc::Rc};
+
+    use proptest::prelude::*;
+
     use super::*;
-    use crate::parser_lib::test_utils::{assert_commit_message_eq_expected, TestTokenBuilder};
+    use crate::parser_lib::{
+        lexing::Lexer,
+        test_utils::{
+            arb_conventional_message, arb_message, assert_commit_message_eq_expected,
+            TestTokenBuilder,
+        },
+    };
+
+    fn parse(message: &str, mode: ParseMode) -> Result<CommitMessage<'static>, SyntaxError> {
+        Parser::process(Lexer::shared(Rc::new(message.to_string())), mode)
+    }
+
+    // Symbols follow each other in message order and their content lies within their tokens
+    fn assert_spans_nested(message: &str, parsed: &CommitMessage) -> Result<(), TestCaseError> {
+        let symbols: Vec<&Symbol> = [
+            &parsed.topic,
+            &parsed.scope,
+            &parsed.description,
+            &parsed.body,
+        ]
+        .into_iter()
+        .flatten()
+        .chain(parsed.footers.iter().flatten())
+        .collect();
+        let mut end = 0;
+        for symbol in symbols {
+            let span = symbol.span();
+            let content = symbol.content_span();
+            prop_assert!(end <= span.start && span.end <= message.len());
+            prop_assert!(span.start <= content.start && content.end <= span.end);
+            prop_assert_eq!(symbol.full_string(), &message[span.clone()]);
+            end = span.end;
+        }
+        for diagnostic in parsed.diagnostics.iter() {
+            prop_assert!(diagnostic.span.end <= message.len());
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn should_never_panic(message in arb_message()) {
+            for mode in [ParseMode::Strict, ParseMode::Lenient] {
+                if let Ok(parsed) = parse(&message, mode) {
+                    assert_spans_nested(&message, &parsed)?;
+                }
+            }
+        }
+
+        #[test]
+        fn should_parse_conventional_messages(message in arb_conventional_message()) {
+            let parsed = parse(&message, ParseMode::Strict);
+            prop_assert!(parsed.is_ok(), "{:?}: {}", message, parsed.err().unwrap());
+            assert_spans_nested(&message, &parsed.unwrap())?;
+        }
+    }
 
     #[test]
     fn should_parse_simple_message() {
@@ -43,7 +211,7 @@ mod tests {
             .description("added a new endpoint for users")
             .generate_vec();
 
-        let parsed = Parser::process(tokens).unwrap();
+        let parsed = Parser::process(tokens, ParseMode::Strict).unwrap();
         assert_commit_message_eq_expected(parsed, expected);
     }
 
@@ -61,10 +229,128 @@ mod tests {
             .colon_footer("test: this is a footer", false)
             .generate_vec();
 
-        let parsed = Parser::process(tokens).unwrap();
+        let parsed = Parser::process(tokens, ParseMode::Strict).unwrap();
         assert_commit_message_eq_expected(parsed, expected)
     }
 
+    #[test]
+    fn should_not_read_colon_without_space_as_footer() {
+        let parsed = parse(
+            "fix: x\n\nsee https://example.com\n\nNote:this\nRefs: #1",
+            ParseMode::Strict,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.body.unwrap().content_string(),
+            "see https://example.com\n\nNote:this"
+        );
+        let footers = parsed.footers.unwrap();
+        assert_eq!(footers.len(), 1);
+        assert_eq!(footers[0].footer_value().unwrap(), "#1");
+
+        // Inside a footer value it continues the value
+        let parsed = parse("fix: x\n\nNote: a\nkey:value", ParseMode::Strict).unwrap();
+        assert_eq!(
+            parsed.footers.unwrap()[0].footer_value().unwrap(),
+            "a\nkey:value"
+        );
+    }
+
+    #[test]
+    fn should_ignore_newlines_at_the_end() {
+        let parsed = parse("fix: x\n\nbody\nmore body\n\n\n", ParseMode::Strict).unwrap();
+        assert_eq!(parsed.body.unwrap().content_string(), "body\nmore body");
+
+        for message in ["fix: x\n", "fix: x\n\n\n"] {
+            let parsed = parse(message, ParseMode::Strict).unwrap();
+            assert_eq!(parsed.description.unwrap().content_string(), "x");
+            assert!(parsed.body.is_none());
+            assert!(parsed.footers.is_none());
+        }
+
+        let parsed = parse("fix: x\n\nRefs: #1\n\n", ParseMode::Strict).unwrap();
+        assert_eq!(parsed.footers.unwrap()[0].footer_value().unwrap(), "#1");
+    }
+
+    #[test]
+    fn should_accept_extra_whitespace_when_lenient() {
+        let message = Rc::new("\n feat ( api ) :  add users".to_string());
+        let tokens: Vec<Token> = Lexer::shared(message).collect();
+        assert!(Parser::process(tokens.clone(), ParseMode::Strict).is_err());
+
+        let parsed = Parser::process(tokens, ParseMode::Lenient).unwrap();
+        assert_eq!(parsed.scope.unwrap().content_string(), "api");
+        assert_eq!(parsed.description.unwrap().content_string(), "add users");
+        let found: Vec<(StyleIssue, Range<usize>)> = parsed
+            .diagnostics
+            .into_iter()
+            .map(|diagnostic| (diagnostic.issue, diagnostic.span))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (StyleIssue::WhitespaceBeforeHeader, 0..2),
+                (StyleIssue::SpaceBeforeScope, 6..7),
+                (StyleIssue::SpaceInScope, 8..9),
+                (StyleIssue::SpaceInScope, 12..13),
+                (StyleIssue::SpaceBeforeColon, 14..15),
+                (StyleIssue::ExtraSpaceAfterColon, 17..18),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_parse_single_word_description_when_lenient() {
+        for message in ["feat(api): x", "feat (api):  x"] {
+            let tokens: Vec<Token> = Lexer::shared(Rc::new(message.to_string())).collect();
+            let parsed = Parser::process(tokens, ParseMode::Lenient).unwrap();
+            assert_eq!(parsed.description.unwrap().content_string(), "x");
+        }
+    }
+
+    #[test]
+    fn should_parse_breaking_change_without_scope() {
+        let message = Rc::new("feat!: drop node 12".to_string());
+        let tokens: Vec<Token> = Lexer::shared(message).collect();
+        let parsed = Parser::process(tokens, ParseMode::Strict).unwrap();
+        assert!(parsed.scope.is_none());
+        assert_eq!(parsed.description.unwrap().full_string(), "!: drop node 12");
+    }
+
+    #[test]
+    fn should_report_space_before_colon_without_scope() {
+        let message = Rc::new("feat ! : add users".to_string());
+        let tokens: Vec<Token> = Lexer::shared(message).collect();
+        let parsed = Parser::process(tokens, ParseMode::Lenient).unwrap();
+        let found: Vec<StyleIssue> = parsed.diagnostics.iter().map(|d| d.issue).collect();
+        assert_eq!(
+            found,
+            [StyleIssue::SpaceBeforeColon, StyleIssue::SpaceBeforeColon]
+        );
+    }
+
+    #[test]
+    fn should_parse_header_without_lexing_the_rest() {
+        let message = "feat(api)!: drop v1\nnot a blank line\n\nRefs: #1";
+        assert!(parse(message, ParseMode::Strict).is_err());
+
+        let pulled = Cell::new(0);
+        let tokens = Lexer::new(message).inspect(|_| pulled.set(pulled.get() + 1));
+        let parsed = Parser::process_header(tokens, ParseMode::Strict).unwrap();
+        assert_eq!(parsed.header(), "feat(api)!: drop v1");
+        assert!(parsed.is_breaking());
+        assert!(parsed.body.is_none() && parsed.footers.is_none());
+        // The newline ending the description is peeked, nothing after it
+        assert_eq!(pulled.get(), 11);
+    }
+
+    #[test]
+    fn should_find_breaking_change_footer() {
+        let parsed = parse("feat: x\n\nBREAKING-CHANGE: gone", ParseMode::Strict).unwrap();
+        assert!(parsed.is_breaking());
+        assert!(!parse("feat: x", ParseMode::Strict).unwrap().is_breaking());
+    }
+
     #[test]
     fn should_parse_message_with_a_lot_of_stuff() {
         let (tokens, expected) = TestTokenBuilder::new()
@@ -91,7 +377,7 @@ mod tests {
             })
             .generate_vec();
 
-        let parsed = Parser::process(tokens).unwrap();
+        let parsed = Parser::process(tokens, ParseMode::Strict).unwrap();
         assert_commit_message_eq_expected(parsed, expected)
     }
 }
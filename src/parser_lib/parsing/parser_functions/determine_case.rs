@@ -19,19 +19,13 @@ macro_rules! generate_match {
     };
 }
 
+/// Case of a word, `Unknown` when it's empty or doesn't start with an ASCII letter
 pub fn determine_text_case(content: &str) -> TextCase {
     let mut char_iter = content.chars();
     let mut found_case: TextCase = match char_iter.next() {
-        Some(c) => {
-            if c.is_ascii_uppercase() {
-                TextCase::Upper
-            } else if c.is_ascii_lowercase() {
-                TextCase::Lower
-            } else {
-                panic!("Invalid character: Word can't begin with '{}'", c);
-            }
-        }
-        None => panic!("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"),
+        Some(c) if c.is_ascii_uppercase() => TextCase::Upper,
+        Some(c) if c.is_ascii_lowercase() => TextCase::Lower,
+        _ => return TextCase::Unknown,
     };
     for char in char_iter {
         found_case = generate_match!(found_case =>
@@ -60,7 +54,12 @@ pub fn determine_text_case(content: &str) -> TextCase {
             (Pascal =>
                 (char.is_ascii_uppercase(), TextCase::Pascal),
                 (char.is_ascii_lowercase(), TextCase::Pascal),
-                (char == '_', TextCase::Scream)
+                (char == '_', TextCase::Scream),
+                (char == '-', TextCase::CapitalizedKebab)
+            ),
+            (CapitalizedKebab =>
+                (char == '-', TextCase::CapitalizedKebab),
+                (char.is_ascii_lowercase(), TextCase::CapitalizedKebab)
             ),
             (Scream =>
                 (char.is_ascii_uppercase(), TextCase::Scream),
@@ -75,7 +74,7 @@ pub fn determine_text_case(content: &str) -> TextCase {
             (Unknown => (true, TextCase::Unknown))
         );
     }
-    return found_case;
+    found_case
 }
 
 #[cfg(test)]
@@ -92,6 +91,10 @@ mod tests {
         assert!(matches!(determine_text_case("TEST TEXT"), TextCase::Scream));
         assert!(matches!(determine_text_case("TEST"), TextCase::Upper));
         assert!(matches!(determine_text_case("test"), TextCase::Lower));
+        assert!(matches!(
+            determine_text_case("Signed-off-by"),
+            TextCase::CapitalizedKebab
+        ));
         assert!(matches!(
             determine_text_case("TesT-TextT"),
             TextCase::Unknown
@@ -99,8 +102,11 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
     fn test_determine_text_case_invalid() {
-        determine_text_case("1testText");
+        assert!(matches!(
+            determine_text_case("1testText"),
+            TextCase::Unknown
+        ));
+        assert!(matches!(determine_text_case(""), TextCase::Unknown));
     }
 }
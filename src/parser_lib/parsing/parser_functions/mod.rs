@@ -2,13 +2,17 @@ mod determine_case;
 mod parse_body;
 mod parse_description;
 mod parse_footer;
+mod parse_gitmoji;
+mod parse_revert;
 mod parse_scope;
 mod parse_topic;
 mod utils;
 
 pub use self::determine_case::determine_text_case;
 pub use self::parse_body::parse_body;
-pub use self::parse_description::parse_description;
+pub use self::parse_description::{parse_description, parse_spaced_description};
 pub use self::parse_footer::parse_footers;
+pub use self::parse_gitmoji::parse_gitmoji_topic;
+pub use self::parse_revert::is_git_revert_header;
 pub use self::parse_scope::parse_scope;
 pub use self::parse_topic::parse_topic;
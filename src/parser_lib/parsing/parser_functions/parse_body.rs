@@ -1,12 +1,12 @@
 use crate::parser_lib::{
     errors::SyntaxError,
     lexing::types::Token,
-    parsing::types::{Symbol, TokenIter},
+    parsing::types::{Symbol, SymbolKind, TokenIter},
 };
 
 use super::utils::{has_double_newline, has_footer_start, take_until_newline_cond};
 
-pub fn parse_start_delimiter(tokens: &mut TokenIter) -> Option<Vec<Token>> {
+pub fn parse_start_delimiter<'a>(tokens: &mut TokenIter<'a>) -> Option<Vec<Token<'a>>> {
     if has_double_newline(tokens) {
         Some(vec![tokens.next().unwrap(), tokens.next().unwrap()])
     } else {
@@ -15,35 +15,57 @@ pub fn parse_start_delimiter(tokens: &mut TokenIter) -> Option<Vec<Token>> {
 }
 
 fn check_end_of_body(tokens: &mut TokenIter) -> Result<bool, SyntaxError> {
-    match tokens.peek() {
+    Ok(match tokens.peek() {
         Some(_) => has_footer_start(tokens),
-        _ => Ok(false),
-    }
+        _ => false,
+    })
 }
 
-pub fn parse_body(tokens: &mut TokenIter) -> Result<Option<Symbol>, SyntaxError> {
-    let start_delimiter = parse_start_delimiter(tokens);
-    match (&start_delimiter, tokens.peek()) {
-        (None, Some(toke)) => {
-            return Err(SyntaxError::UnexpectedToken(
-                toke.to_owned().clone(),
-                "No newline before body".to_string(),
-            ))
-        }
-        (None, None) => return Ok(None),
-        (Some(_), _) => (),
+pub fn parse_body<'a>(tokens: &mut TokenIter<'a>) -> Result<Option<Symbol<'a>>, SyntaxError> {
+    let Some(start_delimiter) = parse_start_delimiter(tokens) else {
+        // Either the message ends after the header or a single newline is followed by the body
+        tokens.reset_peek();
+        let body_start = match tokens.peek() {
+            Some(_) => tokens.peek().cloned(),
+            None => None,
+        };
+        tokens.reset_peek();
+        return match body_start {
+            Some(token) => Err(SyntaxError::unexpected(
+                token,
+                "a blank line before the body",
+            )),
+            None => Ok(None),
+        };
     };
-    let text_tokens = take_until_newline_cond(tokens, check_end_of_body)?;
 
-    Ok(Some(Symbol::Body {
-        start_delimiter: start_delimiter.unwrap(),
-        text_tokens,
-    }))
+    // Footers right after the header mean there is no body
+    if has_footer_start(tokens) {
+        return Ok(None);
+    }
+
+    let mut text_tokens = take_until_newline_cond(tokens, check_end_of_body)?;
+    // The blank line before the footers separates them from the body, it's not part of it
+    let content_len = text_tokens
+        .iter()
+        .rposition(|token| !matches!(token, Token::Newline(_)))
+        .map_or(0, |i| i + 1);
+    if content_len == 0 {
+        return Ok(None);
+    }
+    let end_delimiter = text_tokens.split_off(content_len);
+    Ok(Some(
+        SymbolKind::Body {
+            start_delimiter,
+            text_tokens,
+            end_delimiter,
+        }
+        .into(),
+    ))
 }
 
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
 
     use crate::parser_lib::test_utils::TestTokenBuilder;
 
@@ -59,13 +81,14 @@ mod tests {
 
         let result = parse_body(&mut tokens);
         let symbol = result.unwrap().unwrap();
-        match &symbol {
-            Symbol::Body {
+        match symbol.kind() {
+            SymbolKind::Body {
                 start_delimiter,
-                text_tokens: _,
+                end_delimiter,
+                ..
             } => {
-                //assert_eq!(text_tokens.len(), 9);
                 assert_eq!(start_delimiter.len(), 2);
+                assert_eq!(end_delimiter.len(), 2);
                 assert_eq!(
                     symbol.full_string(),
                     "\n\nthis is a body\n\n",
@@ -73,7 +96,7 @@ mod tests {
                 );
                 assert_eq!(
                     symbol.content_string(),
-                    "this is a body\n\n",
+                    "this is a body",
                     "symbol.no_delims_string()"
                 );
             }
@@ -85,7 +108,7 @@ mod tests {
 
     #[test]
     fn should_parse_multi_line_body() {
-        let (tokens, _) = TestTokenBuilder::new()
+        let (mut tokens, _) = TestTokenBuilder::new()
             .body(|builder| {
                 builder
                     .string("this is a body")
@@ -97,14 +120,28 @@ mod tests {
                     .space()
                     .string("yupyup")
             })
-            .generate_vec();
+            .generate_iter();
 
-        let result = parse_body(&mut tokens.into_iter().multipeek());
+        let result = parse_body(&mut tokens);
         let symbol = result.unwrap().unwrap();
         let expected_str = "\n\nthis is a body\n with multiple lines\n\n yupyup".to_string();
         assert_eq!(symbol.full_string(), expected_str,);
     }
 
+    #[test]
+    fn should_not_parse_footer_after_header_as_body() {
+        let (mut tokens, _) = TestTokenBuilder::new()
+            .newline()
+            .newline()
+            .colon_footer("test: this is a footer", false)
+            .generate_iter();
+
+        let result = parse_body(&mut tokens);
+        assert!(result.unwrap().is_none());
+        let left_str = tokens.map(|t| t.get_value()).collect::<String>();
+        assert_eq!(left_str, "test: this is a footer");
+    }
+
     #[test]
     fn should_leave_footer_alone() {
         let (mut tokens, _) = TestTokenBuilder::new()
@@ -116,11 +153,69 @@ mod tests {
         let result = parse_body(&mut tokens);
         let symbol = result.unwrap().unwrap();
         let left: Vec<Token> = tokens.collect();
-        assert!(left.len() > 0);
+        assert!(!left.is_empty());
         assert_eq!(left.len(), 11);
-        assert_eq!(symbol.content_string(), "this is a body\n");
+        assert_eq!(symbol.content_string(), "this is a body");
         assert_eq!(symbol.full_string(), "\n\nthis is a body\n");
         let left_str = left.iter().map(|t| t.get_value()).collect::<String>();
         assert_eq!(left_str, "test: this is a footer\n");
     }
+
+    #[test]
+    fn should_keep_lines_with_a_colon_but_no_space_in_the_body() {
+        let (mut tokens, _) = TestTokenBuilder::new()
+            .body(|builder| {
+                builder
+                    .word("https")
+                    .colon()
+                    .word("//example.com")
+                    .newline()
+                    .word("Note")
+                    .colon()
+                    .word("this")
+            })
+            .generate_iter();
+        let symbol = parse_body(&mut tokens).unwrap().unwrap();
+        assert_eq!(symbol.content_string(), "https://example.com\nNote:this");
+        assert!(tokens.next().is_none());
+    }
+
+    #[test]
+    fn should_not_parse_blank_lines_before_footers_as_body() {
+        let (mut tokens, _) = TestTokenBuilder::new()
+            .newline()
+            .newline()
+            .newline()
+            .newline()
+            .colon_footer("test: this is a footer", false)
+            .generate_iter();
+        assert!(parse_body(&mut tokens).unwrap().is_none());
+        let left_str = tokens.map(|t| t.get_value()).collect::<String>();
+        assert_eq!(left_str, "test: this is a footer");
+    }
+
+    #[test]
+    fn should_point_at_body_without_blank_line() {
+        let (mut tokens, _) = TestTokenBuilder::new()
+            .newline()
+            .string("the body")
+            .generate_iter();
+        let Err(SyntaxError::UnexpectedToken(unexpected)) = parse_body(&mut tokens) else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!(unexpected.found(), "the");
+        assert_eq!(unexpected.expected(), "a blank line before the body");
+    }
+
+    #[test]
+    fn should_parse_long_body_without_recursing() {
+        let mut builder = TestTokenBuilder::new();
+        builder.newline().newline();
+        for _ in 0..100_000 {
+            builder.string("pasted log line").newline();
+        }
+        let (mut tokens, _) = builder.generate_iter();
+        let symbol = parse_body(&mut tokens).unwrap().unwrap();
+        assert_eq!(symbol.content_string().lines().count(), 100_000);
+    }
 }
@@ -3,10 +3,11 @@ use itertools::Itertools;
 use crate::parser_lib::{
     errors::SyntaxError,
     lexing::types::Token,
-    parsing::types::{Symbol, TokenIter},
+    parsing::types::{Diagnostics, StyleIssue, Symbol, SymbolKind, TokenIter},
 };
 
-fn check_bang(tokens: &mut TokenIter) -> Result<Option<Token>, SyntaxError> {
+fn check_bang<'a>(tokens: &mut TokenIter<'a>) -> Result<Option<Token<'a>>, SyntaxError> {
+    tokens.reset_peek();
     let current = tokens.peek();
     match current {
         Some(Token::Bang(_)) => Ok(tokens.next()),
@@ -15,7 +16,7 @@ fn check_bang(tokens: &mut TokenIter) -> Result<Option<Token>, SyntaxError> {
     }
 }
 
-fn check_start_delimiter(tokens: &mut TokenIter) -> Result<Vec<Token>, SyntaxError> {
+fn check_start_delimiter<'a>(tokens: &mut TokenIter<'a>) -> Result<Vec<Token<'a>>, SyntaxError> {
     let current = tokens.next();
     let next = tokens.peek();
     match (&current, next) {
@@ -32,39 +33,67 @@ fn check_start_delimiter(tokens: &mut TokenIter) -> Result<Vec<Token>, SyntaxErr
     }
 }
 
-fn take_words(tokens: &mut TokenIter) -> Result<Vec<Token>, SyntaxError> {
+fn take_words<'a>(tokens: &mut TokenIter<'a>) -> Result<Vec<Token<'a>>, SyntaxError> {
     let next = tokens.peek();
     if next.is_none() {
         return Err(SyntaxError::UnexpectedEndOfFile);
     }
 
     Ok(tokens
-        .take_while_ref(|token| !matches!(token, Token::Newline(_)))
+        .peeking_take_while(|token| !matches!(token, Token::Newline(_)))
         .collect_vec())
 }
 
-pub fn parse_description(tokens: &mut TokenIter) -> Result<Symbol, SyntaxError> {
+pub fn parse_description<'a>(
+    tokens: &mut TokenIter<'a>,
+    diagnostics: &mut Diagnostics,
+) -> Result<Symbol<'a>, SyntaxError> {
+    diagnostics.skip_spaces(tokens, StyleIssue::SpaceBeforeColon);
     let braking_change_token = check_bang(tokens)?;
+    diagnostics.skip_spaces(tokens, StyleIssue::SpaceBeforeColon);
     let start_delimiter = check_start_delimiter(tokens)?;
+    diagnostics.skip_spaces(tokens, StyleIssue::ExtraSpaceAfterColon);
     let text_tokens = take_words(tokens)?;
 
-    Ok(Symbol::Description {
+    Ok(SymbolKind::Description {
         start_delimiter,
         text_tokens,
         braking_change_token,
-    })
+    }
+    .into())
+}
+
+/// Description separated from the topic by a space only,
+/// as in `Revert "feat: x"` or `✨ add users`
+pub fn parse_spaced_description<'a>(tokens: &mut TokenIter<'a>) -> Result<Symbol<'a>, SyntaxError> {
+    tokens.reset_peek();
+    let start_delimiter = match tokens.next() {
+        Some(space @ Token::Space(_)) => vec![space],
+        Some(token) => return Err(SyntaxError::expected_space(token)),
+        None => return Err(SyntaxError::UnexpectedEndOfFile),
+    };
+    let text_tokens = take_words(tokens)?;
+
+    Ok(SymbolKind::Description {
+        start_delimiter,
+        text_tokens,
+        braking_change_token: None,
+    }
+    .into())
 }
 
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
 
-    use crate::parser_lib::{parsing::types::Symbol, test_utils::TestTokenBuilder};
+    use crate::parser_lib::{
+        parsing::types::{Diagnostics, ParseMode, SymbolKind},
+        test_utils::TestTokenBuilder,
+    };
 
     use super::parse_description;
     #[test]
     fn should_parse_description() {
-        let (tokens, _) = TestTokenBuilder::new()
+        let (mut tokens, _) = TestTokenBuilder::new()
             .colon()
             .space()
             .word("description")
@@ -74,12 +103,11 @@ mod tests {
             .word("this")
             .newline()
             .newline()
-            .generate_vec();
-        dbg!(&tokens);
-        let res = parse_description(&mut tokens.into_iter().multipeek());
+            .generate_iter();
+        let res = parse_description(&mut tokens, &mut Diagnostics::new(ParseMode::Strict));
         let symbol = res.unwrap();
-        match &symbol {
-            Symbol::Description {
+        match symbol.kind() {
+            SymbolKind::Description {
                 start_delimiter,
                 text_tokens,
                 braking_change_token: _,
@@ -98,7 +126,7 @@ mod tests {
         let (mut tokens, _) = TestTokenBuilder::new()
             .description_with_bang("description is this")
             .generate_iter();
-        let res = parse_description(&mut tokens);
+        let res = parse_description(&mut tokens, &mut Diagnostics::new(ParseMode::Strict));
         let symbol = res.unwrap();
         assert_eq!(symbol.full_string(), "!: description is this");
     }
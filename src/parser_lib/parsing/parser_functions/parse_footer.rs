@@ -1,57 +1,37 @@
 use crate::parser_lib::{
     errors::SyntaxError,
     lexing::types::Token,
-    parsing::types::{Symbol, TokenIter},
+    parsing::types::{Symbol, SymbolKind, TokenIter},
 };
 
-use super::utils::{has_footer_start, take_until_newline_cond};
+use super::utils::{footer_start_len, has_footer_start, take_until_newline_cond};
 
-fn parse_key(tokens: &mut TokenIter) -> Result<Option<Vec<Token>>, SyntaxError> {
-    if has_footer_start(tokens)? {
-        let key = vec![
-            tokens.next().unwrap(),
-            tokens.next().unwrap(),
-            tokens.next().unwrap(),
-        ];
-        return Ok(Some(key));
-    }
-    Ok(None)
+fn parse_key<'a>(tokens: &mut TokenIter<'a>) -> Option<Vec<Token<'a>>> {
+    footer_start_len(tokens).map(|len| tokens.by_ref().take(len).collect())
 }
 
-fn parse_footer_text(tokens: &mut TokenIter) -> Result<Vec<Token>, SyntaxError> {
+fn parse_footer_text<'a>(tokens: &mut TokenIter<'a>) -> Result<Vec<Token<'a>>, SyntaxError> {
     take_until_newline_cond(tokens, |remaining| match remaining.peek() {
         Some(Token::Newline(_)) => Err(SyntaxError::expected_string(remaining.next().unwrap())),
-        Some(_) => Ok(has_footer_start(remaining)?),
+        Some(_) => Ok(has_footer_start(remaining)),
         None => Ok(true),
     })
 }
 
-// This runs after every new line
-fn recurse_footers(tokens: &mut TokenIter) -> Result<Option<Vec<Symbol>>, SyntaxError> {
-    match parse_key(tokens)? {
-        None => Ok(None),
-        Some(key) => {
-            let footer = Symbol::Footer {
+pub fn parse_footers<'a>(
+    tokens: &mut TokenIter<'a>,
+) -> Result<Option<Vec<Symbol<'a>>>, SyntaxError> {
+    let mut footers: Vec<Symbol> = Vec::new();
+    while let Some(key) = parse_key(tokens) {
+        footers.push(
+            SymbolKind::Footer {
                 start_delimiter: key,
                 text_tokens: parse_footer_text(tokens)?,
-            };
-
-            let mut footers: Vec<Symbol> = Vec::new();
-            footers.push(footer);
-
-            if has_footer_start(tokens)? {
-                if let Some(next_footer) = recurse_footers(tokens)? {
-                    footers.extend(next_footer);
-                }
             }
-
-            Ok(Some(footers))
-        }
+            .into(),
+        );
     }
-}
-
-pub fn parse_footers(tokens: &mut TokenIter) -> Result<Option<Vec<Symbol>>, SyntaxError> {
-    recurse_footers(tokens)
+    Ok((!footers.is_empty()).then_some(footers))
 }
 
 #[cfg(test)]
@@ -69,7 +49,7 @@ mod tests {
             .word("this")
             .generate_iter();
         let symbol = parse_footers(&mut tokens).unwrap().unwrap().pop().unwrap();
-        assert!(matches!(symbol, Symbol::Footer { .. }));
+        assert!(matches!(symbol.kind(), SymbolKind::Footer { .. }));
         assert_eq!(symbol.content_string(), "footer: this");
     }
 
@@ -88,9 +68,42 @@ mod tests {
             .generate_iter();
         let symbols = parse_footers(&mut tokens).unwrap().unwrap();
         assert_eq!(symbols.len(), 2);
-        assert!(matches!(symbols[0], Symbol::Footer { .. }));
-        assert!(matches!(symbols[1], Symbol::Footer { .. }));
+        assert!(matches!(symbols[0].kind(), SymbolKind::Footer { .. }));
+        assert!(matches!(symbols[1].kind(), SymbolKind::Footer { .. }));
         assert_eq!(symbols[0].content_string(), "footer: this\n");
         assert_eq!(symbols[1].content_string(), "another-footer #12");
     }
+
+    #[test]
+    fn should_parse_breaking_change_footer_with_space() {
+        let (mut tokens, _) = TestTokenBuilder::new()
+            .word("BREAKING")
+            .space()
+            .word("CHANGE")
+            .colon()
+            .space()
+            .word("gone")
+            .newline()
+            .word("Refs")
+            .colon()
+            .space()
+            .word("#1")
+            .generate_iter();
+        let symbols = parse_footers(&mut tokens).unwrap().unwrap();
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[0].footer_key().unwrap(), "BREAKING CHANGE");
+        assert_eq!(symbols[0].footer_value().unwrap(), "gone");
+        assert_eq!(symbols[1].footer_key().unwrap(), "Refs");
+    }
+
+    #[test]
+    fn should_parse_many_footers_without_recursing() {
+        let mut builder = TestTokenBuilder::new();
+        for _ in 0..100_000 {
+            builder.word("Refs").colon().space().word("12").newline();
+        }
+        let (mut tokens, _) = builder.generate_iter();
+        let symbols = parse_footers(&mut tokens).unwrap().unwrap();
+        assert_eq!(symbols.len(), 100_000);
+    }
 }
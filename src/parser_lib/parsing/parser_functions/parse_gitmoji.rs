@@ -0,0 +1,89 @@
+use crate::parser_lib::{
+    lexing::types::{Token, WordDetails},
+    parsing::types::{Symbol, SymbolKind, TokenIter},
+};
+
+fn is_emoji(word: &str) -> bool {
+    word.chars()
+        .next()
+        .is_some_and(|c| !c.is_ascii() && !c.is_alphanumeric())
+}
+
+/// A gitmoji in place of the topic, either as `:sparkles:` or as `✨`, followed by a space.
+/// The shortcode becomes a single topic token including its colons.
+pub fn parse_gitmoji_topic<'a>(tokens: &mut TokenIter<'a>) -> Option<Symbol<'a>> {
+    tokens.reset_peek();
+    let first = tokens.peek().cloned();
+    let second = tokens.peek().cloned();
+    let third = tokens.peek().cloned();
+    let fourth = tokens.peek().cloned();
+    tokens.reset_peek();
+
+    match (first, second, third, fourth) {
+        (
+            Some(start @ Token::Colon(_)),
+            Some(Token::Word(_)),
+            Some(end @ Token::Colon(_)),
+            Some(Token::Space(_)),
+        ) => {
+            tokens.next();
+            tokens.next();
+            tokens.next();
+            let shortcode = start.get_super_slice(start.get_start_index()..end.get_end_index());
+            Some(
+                SymbolKind::Topic {
+                    text_token: Token::Word(WordDetails::new(shortcode)),
+                }
+                .into(),
+            )
+        }
+        (Some(word @ Token::Word(_)), Some(Token::Space(_)), _, _)
+            if is_emoji(&word.get_value()) =>
+        {
+            tokens.next();
+            Some(SymbolKind::Topic { text_token: word }.into())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser_lib::test_utils::TestTokenBuilder;
+
+    #[test]
+    fn should_parse_shortcode_topic() {
+        let (mut tokens, _) = TestTokenBuilder::new()
+            .colon()
+            .word("sparkles")
+            .colon()
+            .space()
+            .word("add")
+            .generate_iter();
+        let topic = parse_gitmoji_topic(&mut tokens).unwrap();
+        assert_eq!(topic.content_string(), ":sparkles:");
+        assert!(matches!(tokens.next(), Some(Token::Space(_))));
+    }
+
+    #[test]
+    fn should_parse_emoji_topic() {
+        let (mut tokens, _) = TestTokenBuilder::new()
+            .word("✨")
+            .space()
+            .word("add")
+            .generate_iter();
+        assert_eq!(
+            parse_gitmoji_topic(&mut tokens).unwrap().content_string(),
+            "✨"
+        );
+
+        let (mut tokens, _) = TestTokenBuilder::new()
+            .word("feat")
+            .colon()
+            .space()
+            .generate_iter();
+        assert!(parse_gitmoji_topic(&mut tokens).is_none());
+        assert!(matches!(tokens.next(), Some(Token::Word(_))));
+    }
+}
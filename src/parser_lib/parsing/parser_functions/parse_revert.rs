@@ -0,0 +1,59 @@
+use crate::parser_lib::{
+    lexing::types::Token,
+    parsing::types::{Symbol, TokenIter},
+};
+
+/// Git's default revert header, `Revert "feat(api): add users"`.
+/// The quoted header of the reverted commit becomes the description.
+pub fn is_git_revert_header(topic: &Symbol, tokens: &mut TokenIter) -> bool {
+    tokens.reset_peek();
+    let result = topic.content_string() == "Revert"
+        && matches!(tokens.peek(), Some(Token::Space(_)))
+        && matches!(tokens.peek(), Some(word @ Token::Word(_)) if word.get_value().starts_with('"'));
+    tokens.reset_peek();
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser_lib::{
+        parsing::parser_functions::{parse_spaced_description, parse_topic},
+        test_utils::TestTokenBuilder,
+    };
+
+    #[test]
+    fn should_parse_git_revert_header() {
+        let (mut tokens, _) = TestTokenBuilder::new()
+            .word("Revert")
+            .space()
+            .word("\"feat")
+            .parenthesis_open()
+            .word("api")
+            .parenthesis_close()
+            .colon()
+            .space()
+            .word("add")
+            .space()
+            .word("users\"")
+            .newline()
+            .generate_iter();
+        let topic = parse_topic(&mut tokens).unwrap();
+        assert!(is_git_revert_header(&topic, &mut tokens));
+        let description = parse_spaced_description(&mut tokens).unwrap();
+        assert_eq!(description.content_string(), "\"feat(api): add users\"");
+        assert!(matches!(tokens.next(), Some(Token::Newline(_))));
+    }
+
+    #[test]
+    fn should_only_accept_quoted_header() {
+        let (mut tokens, _) = TestTokenBuilder::new()
+            .word("Revert")
+            .space()
+            .word("things")
+            .generate_iter();
+        let topic = parse_topic(&mut tokens).unwrap();
+        assert!(!is_git_revert_header(&topic, &mut tokens));
+    }
+}
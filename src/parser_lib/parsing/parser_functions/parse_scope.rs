@@ -1,23 +1,23 @@
 use crate::parser_lib::{
     errors::SyntaxError,
     lexing::types::Token,
-    parsing::types::{Symbol, TokenIter},
+    parsing::types::{Diagnostics, StyleIssue, Symbol, SymbolKind, TokenIter},
 };
 
-fn check_start_delimiter(tokens: &mut TokenIter) -> Result<Option<Token>, SyntaxError> {
+fn check_start_delimiter<'a>(tokens: &mut TokenIter<'a>) -> Result<Option<Token<'a>>, SyntaxError> {
     let current = tokens.peek();
     match current {
         Some(Token::ParenthesisOpen(_)) => Ok(tokens.next()),
-        Some(Token::Colon(_)) => Ok(None),
-        Some(_token) => Err(SyntaxError::UnexpectedToken(
+        Some(Token::Colon(_) | Token::Bang(_)) => Ok(None),
+        Some(_token) => Err(SyntaxError::unexpected(
             tokens.next().unwrap(),
-            "'(' or ':'".to_string(),
+            "'(' or ':'",
         )),
         None => Err(SyntaxError::UnexpectedEndOfFile),
     }
 }
 
-fn take_word(tokens: &mut TokenIter) -> Result<Token, SyntaxError> {
+fn take_word<'a>(tokens: &mut TokenIter<'a>) -> Result<Token<'a>, SyntaxError> {
     let current = tokens.next();
     match current {
         Some(Token::Word(_)) => Ok(current.unwrap()),
@@ -26,7 +26,7 @@ fn take_word(tokens: &mut TokenIter) -> Result<Token, SyntaxError> {
     }
 }
 
-fn check_end_delimiter(tokens: &mut TokenIter) -> Result<Token, SyntaxError> {
+fn check_end_delimiter<'a>(tokens: &mut TokenIter<'a>) -> Result<Token<'a>, SyntaxError> {
     let current = tokens.next();
     match current {
         Some(Token::ParenthesisClose(_)) => Ok(current.unwrap()),
@@ -35,26 +35,46 @@ fn check_end_delimiter(tokens: &mut TokenIter) -> Result<Token, SyntaxError> {
     }
 }
 
-pub fn parse_scope(tokens: &mut TokenIter) -> Result<Option<Symbol>, SyntaxError> {
+pub fn parse_scope<'a>(
+    tokens: &mut TokenIter<'a>,
+    diagnostics: &mut Diagnostics,
+) -> Result<Option<Symbol<'a>>, SyntaxError> {
+    diagnostics.skip_spaces_before(tokens, StyleIssue::SpaceBeforeScope, |token| {
+        matches!(token, Token::ParenthesisOpen(_))
+    });
+    // Spaces before the colon, parse_description reports them
+    if diagnostics.is_lenient() && matches!(tokens.peek(), Some(Token::Space(_))) {
+        tokens.reset_peek();
+        return Ok(None);
+    }
+    tokens.reset_peek();
     let start_delimiter = check_start_delimiter(tokens)?;
 
     if start_delimiter.is_none() {
         return Ok(None);
     }
 
+    diagnostics.skip_spaces(tokens, StyleIssue::SpaceInScope);
     let word = take_word(tokens)?;
+    diagnostics.skip_spaces(tokens, StyleIssue::SpaceInScope);
     let end_delimiter = check_end_delimiter(tokens)?;
-    Ok(Some(Symbol::Scope {
-        text_token: word,
-        start_delimiter: start_delimiter.unwrap(),
-        end_delimiter,
-    }))
+    Ok(Some(
+        SymbolKind::Scope {
+            text_token: word,
+            start_delimiter: start_delimiter.unwrap(),
+            end_delimiter,
+        }
+        .into(),
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::parser_lib::{
-        parsing::{parser_functions::parse_scope, types::Symbol},
+        parsing::{
+            parser_functions::parse_scope,
+            types::{Diagnostics, ParseMode, SymbolKind},
+        },
         test_utils::TestTokenBuilder,
     };
 
@@ -65,9 +85,9 @@ mod tests {
             .word("scope")
             .parenthesis_close()
             .generate_iter();
-        let res = parse_scope(&mut tokens);
+        let res = parse_scope(&mut tokens, &mut Diagnostics::new(ParseMode::Strict));
         let symbol = res.unwrap().unwrap();
-        assert!(matches!(symbol, Symbol::Scope { .. }));
+        assert!(matches!(symbol.kind(), SymbolKind::Scope { .. }));
         assert_eq!(symbol.content_length(), 5);
         assert_eq!(symbol.total_length(), 7);
         assert_eq!(symbol.full_string(), "(scope)");
@@ -1,15 +1,16 @@
 use crate::parser_lib::{
     errors::SyntaxError,
     lexing::types::Token,
-    parsing::types::{Symbol, TokenIter},
+    parsing::types::{Symbol, SymbolKind, TokenIter},
 };
 // Type is a single word, a bang is the only char we're interested in
-pub fn parse_topic(tokens: &mut TokenIter) -> Result<Symbol, SyntaxError> {
+pub fn parse_topic<'a>(tokens: &mut TokenIter<'a>) -> Result<Symbol<'a>, SyntaxError> {
     let current = tokens.next();
     match current {
-        Some(Token::Word(_)) => Ok(Symbol::Topic {
+        Some(Token::Word(_)) => Ok(SymbolKind::Topic {
             text_token: current.unwrap(),
-        }),
+        }
+        .into()),
         Some(token) => Err(SyntaxError::expected_string(token)),
         None => Err(SyntaxError::UnexpectedEndOfFile),
     }
@@ -25,7 +26,7 @@ mod tests {
         let (mut tokens, _) = TestTokenBuilder::new().word("feat").generate_iter();
         let res = parse_topic(&mut tokens);
         let symbol = res.unwrap();
-        assert!(matches!(symbol, Symbol::Topic { .. }));
+        assert!(matches!(symbol.kind(), SymbolKind::Topic { .. }));
         assert_eq!(symbol.content_length(), 4);
         assert_eq!(symbol.full_string(), "feat");
     }
@@ -37,7 +38,7 @@ mod tests {
         assert!(matches!(res, Err(SyntaxError::UnexpectedToken(..))));
         assert_eq!(
             res.unwrap_err().to_string(),
-            "Syntax error: Unexpected token ':' at index 0. Expected a string."
+            "Syntax error: Unexpected token ':' at line 1, column 1. Expected a string."
         )
     }
 }
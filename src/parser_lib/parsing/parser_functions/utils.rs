@@ -2,56 +2,73 @@ use itertools::Itertools;
 
 use crate::parser_lib::{errors::SyntaxError, lexing::types::Token, parsing::types::TokenIter};
 
-pub fn has_footer_start(orig: &mut TokenIter) -> Result<bool, SyntaxError> {
-    orig.reset_peek();
-    let mut tokens = orig.clone();
-    let t0 = tokens.next();
-    let t1 = tokens.next();
-    let t2 = tokens.next();
-    let t3 = tokens.next();
-    match (t0, t1, &t2, t3) {
-        (Some(Token::Word(_)), Some(Token::Colon(_)), Some(Token::Space(_)), Some(_)) => Ok(true),
+// `BREAKING CHANGE: ` is the one footer key the spec allows a space in
+fn has_breaking_change_start(tokens: &mut TokenIter) -> bool {
+    tokens.reset_peek();
+    let mut next = || tokens.peek().cloned();
+    let result = match (next(), next(), next(), next(), next(), next()) {
+        (
+            Some(breaking @ Token::Word(_)),
+            Some(Token::Space(_)),
+            Some(change @ Token::Word(_)),
+            Some(Token::Colon(_)),
+            Some(Token::Space(_)),
+            Some(_),
+        ) => breaking.as_str() == "BREAKING" && change.as_str() == "CHANGE",
+        _ => false,
+    };
+    tokens.reset_peek();
+    result
+}
+
+/// Number of tokens of the footer key and separator the tokens start with, if they start a footer.
+/// Looks at most six tokens ahead and leaves the peek position reset.
+/// `word:x` isn't a footer, a line like `https://example.com` is just text.
+pub fn footer_start_len(tokens: &mut TokenIter) -> Option<usize> {
+    if has_breaking_change_start(tokens) {
+        return Some(5);
+    }
 
+    let t0 = tokens.peek().cloned();
+    let t1 = tokens.peek().cloned();
+    let t2 = tokens.peek().cloned();
+    let t3 = tokens.peek().cloned();
+    tokens.reset_peek();
+    match (t0, t1, t2, t3) {
+        (Some(Token::Word(_)), Some(Token::Colon(_)), Some(Token::Space(_)), Some(_)) => Some(3),
         (
             Some(Token::Word(_)),
             Some(Token::Space(_)),
             Some(Token::Hash(_)),
             Some(Token::Word(_)),
-        ) => Ok(true),
-
-        (Some(Token::Word(_)), Some(Token::Colon(_)), Some(_), _) => {
-            Err(SyntaxError::expected_space(t2.unwrap()))
-        }
-        _ => Ok(false),
+        ) => Some(3),
+        _ => None,
     }
 }
 
+pub fn has_footer_start(tokens: &mut TokenIter) -> bool {
+    footer_start_len(tokens).is_some()
+}
+
 pub type BreakoutFunction = fn(&mut TokenIter) -> Result<bool, SyntaxError>;
 
-pub fn take_until_newline_cond(
-    tokens: &mut TokenIter,
+/// Takes whole lines until `breakout` is true after a newline or the tokens run out
+pub fn take_until_newline_cond<'a>(
+    tokens: &mut TokenIter<'a>,
     breakout: BreakoutFunction,
-) -> Result<Vec<Token>, SyntaxError> {
-    let mut text_tokens: Vec<Token> = tokens
-        .take_while_ref(|token| !matches!(token, Token::Newline(_)))
-        .collect();
-
-    let newline = tokens.next();
-
-    match &newline {
-        Some(Token::Newline(_)) => {
-            text_tokens.push(newline.unwrap());
-            if breakout(tokens)? {
-                return Ok(text_tokens);
-            }
-            text_tokens.extend(take_until_newline_cond(tokens, breakout)?);
-            Ok(text_tokens)
+) -> Result<Vec<Token<'a>>, SyntaxError> {
+    let mut text_tokens: Vec<Token> = Vec::new();
+    loop {
+        // Unlike `take_while_ref` this doesn't clone the remaining tokens for every token taken
+        text_tokens.extend(tokens.peeking_take_while(|token| !matches!(token, Token::Newline(_))));
+        // Taking stopped at a newline or the end of the tokens
+        let Some(newline) = tokens.next() else {
+            return Ok(text_tokens);
+        };
+        text_tokens.push(newline);
+        if breakout(tokens)? {
+            return Ok(text_tokens);
         }
-        Some(token) => Err(SyntaxError::UnexpectedToken(
-            token.to_owned().clone(),
-            "this not to get here in the first place.".to_string(),
-        )),
-        None => Ok(text_tokens),
     }
 }
 
@@ -0,0 +1,128 @@
+use std::{fmt::Display, ops::Range};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::parser_lib::lexing::types::Token;
+
+use super::TokenIter;
+
+/// How strictly the parser treats whitespace in the header
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParseMode {
+    /// Whitespace has to be exactly as the spec shows it
+    #[default]
+    Strict,
+    /// Accept extra whitespace and report it as style diagnostics
+    Lenient,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum StyleIssue {
+    WhitespaceBeforeHeader,
+    SpaceBeforeScope,
+    SpaceInScope,
+    SpaceBeforeColon,
+    ExtraSpaceAfterColon,
+}
+
+impl Display for StyleIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            StyleIssue::WhitespaceBeforeHeader => "Blank lines or spaces before the header",
+            StyleIssue::SpaceBeforeScope => "Extra space before scope",
+            StyleIssue::SpaceInScope => "Extra space inside scope parentheses",
+            StyleIssue::SpaceBeforeColon => "Extra space before colon",
+            StyleIssue::ExtraSpaceAfterColon => "Extra space after colon",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// Whitespace the lenient parser skipped. Removing the span fixes it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleDiagnostic {
+    pub(crate) issue: StyleIssue,
+    pub(crate) span: Range<usize>,
+}
+
+impl StyleDiagnostic {
+    pub fn issue(&self) -> StyleIssue {
+        self.issue
+    }
+
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// Collects style diagnostics while parsing, skips nothing in strict mode
+pub struct Diagnostics {
+    mode: ParseMode,
+    found: Vec<StyleDiagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new(mode: ParseMode) -> Self {
+        Self {
+            mode,
+            found: Vec::new(),
+        }
+    }
+
+    pub fn is_lenient(&self) -> bool {
+        self.mode == ParseMode::Lenient
+    }
+
+    /// Skips whitespace tokens matching `is_skipped` when lenient, reporting them as `issue`
+    pub fn skip(
+        &mut self,
+        tokens: &mut TokenIter,
+        issue: StyleIssue,
+        is_skipped: fn(&Token) -> bool,
+    ) {
+        if !self.is_lenient() {
+            return;
+        }
+        tokens.reset_peek();
+        let skipped = tokens.peeking_take_while(is_skipped).collect_vec();
+        // Peeking at the token that ended the run moved the cursor past it
+        tokens.reset_peek();
+        if let (Some(first), Some(last)) = (skipped.first(), skipped.last()) {
+            self.found.push(StyleDiagnostic {
+                issue,
+                span: first.get_start_index()..last.get_end_index(),
+            });
+        }
+    }
+
+    pub fn skip_spaces(&mut self, tokens: &mut TokenIter, issue: StyleIssue) {
+        self.skip(tokens, issue, |token| matches!(token, Token::Space(_)));
+    }
+
+    /// Skips spaces only when the token after them matches `next`
+    pub fn skip_spaces_before(
+        &mut self,
+        tokens: &mut TokenIter,
+        issue: StyleIssue,
+        next: fn(&Token) -> bool,
+    ) {
+        tokens.reset_peek();
+        let mut peeked = tokens.peek();
+        while matches!(peeked, Some(Token::Space(_))) {
+            peeked = tokens.peek();
+        }
+        let matches_next = peeked.is_some_and(next);
+        tokens.reset_peek();
+
+        if matches_next {
+            self.skip_spaces(tokens, issue);
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<StyleDiagnostic> {
+        self.found
+    }
+}
@@ -1,13 +1,15 @@
+mod diagnostics;
 mod parser_result;
 mod symbol;
 
-use std::vec::IntoIter;
-
 use itertools::MultiPeek;
 
 use crate::parser_lib::lexing::types::Token;
 
+pub use self::diagnostics::{Diagnostics, ParseMode, StyleDiagnostic, StyleIssue};
 pub use self::parser_result::*;
 pub use self::symbol::Symbol;
+pub(crate) use self::symbol::SymbolKind;
 
-pub type TokenIter = MultiPeek<IntoIter<Token>>;
+/// Tokens straight from the lexer or from a list, e.g. in tests
+pub type TokenIter<'a> = MultiPeek<Box<dyn Iterator<Item = Token<'a>> + 'a>>;
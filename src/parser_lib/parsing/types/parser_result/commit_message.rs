@@ -1,17 +1,122 @@
-use crate::parser_lib::parsing::types::Symbol;
+use std::fmt::Display;
 
+use serde::{Deserialize, Serialize};
+
+use crate::parser_lib::parsing::types::{
+    IssueReference, Revert, StyleDiagnostic, Symbol, SymbolKind,
+};
+
+/// A parsed commit message, made by `CommitMessageParser::process`
 #[derive(Debug, Clone)]
-pub struct CommitMessage {
-    pub topic: Option<Symbol>,
-    pub scope: Option<Symbol>,
-    pub description: Option<Symbol>,
-    pub body: Option<Symbol>,
-    pub footers: Option<Vec<Symbol>>,
+pub struct CommitMessage<'a> {
+    pub(crate) topic: Option<Symbol<'a>>,
+    pub(crate) scope: Option<Symbol<'a>>,
+    pub(crate) description: Option<Symbol<'a>>,
+    pub(crate) body: Option<Symbol<'a>>,
+    pub(crate) footers: Option<Vec<Symbol<'a>>>,
+    /// Whitespace the lenient parse mode accepted
+    pub(crate) diagnostics: Vec<StyleDiagnostic>,
+    /// A breaking change footer found by scanning the lines, for when footers aren't parsed
+    pub(crate) breaking_footer: bool,
 }
 
-#[derive(Debug, Clone)]
+impl<'a> CommitMessage<'a> {
+    /// Looks for a `BREAKING CHANGE: ` footer without parsing the message. Footers and git
+    /// trailers both live in the last paragraph, so body lines above it aren't matched.
+    pub(crate) fn scan_breaking_footer(&mut self, message: &str) {
+        let lines: Vec<&str> = message.trim_end_matches(['\r', '\n']).lines().collect();
+        let last_paragraph = match lines.iter().rposition(|line| line.trim().is_empty()) {
+            Some(blank) => &lines[blank + 1..],
+            None => &[],
+        };
+        self.breaking_footer = last_paragraph.iter().any(|line| {
+            line.starts_with("BREAKING CHANGE: ") || line.starts_with("BREAKING-CHANGE: ")
+        });
+    }
+
+    pub fn topic(&self) -> Option<&Symbol<'a>> {
+        self.topic.as_ref()
+    }
+
+    pub fn scope(&self) -> Option<&Symbol<'a>> {
+        self.scope.as_ref()
+    }
+
+    pub fn description(&self) -> Option<&Symbol<'a>> {
+        self.description.as_ref()
+    }
+
+    pub fn body(&self) -> Option<&Symbol<'a>> {
+        self.body.as_ref()
+    }
+
+    /// Footers in message order, empty when there are none
+    pub fn footers(&self) -> &[Symbol<'a>] {
+        self.footers.as_deref().unwrap_or_default()
+    }
+
+    /// Whitespace the lenient parse mode accepted
+    pub fn diagnostics(&self) -> &[StyleDiagnostic] {
+        &self.diagnostics
+    }
+
+    /// The whole message the symbols were parsed from
+    pub fn source(&self) -> Option<&str> {
+        let topic = self.topic.as_ref()?;
+        topic
+            .get_all_tokens()
+            .first()
+            .map(|token| token.get_source())
+    }
+
+    /// The first line of the message
+    pub fn header(&self) -> String {
+        [&self.topic, &self.scope, &self.description]
+            .into_iter()
+            .flatten()
+            .map(|symbol| symbol.full_string())
+            .collect()
+    }
+
+    /// Marked breaking by a `!` before the colon or a `BREAKING CHANGE` footer
+    pub fn is_breaking(&self) -> bool {
+        let bang = matches!(
+            self.description.as_ref().map(Symbol::kind),
+            Some(SymbolKind::Description {
+                braking_change_token: Some(_),
+                ..
+            })
+        );
+        bang || self.breaking_footer
+            || self.footers().iter().any(|footer| {
+                footer
+                    .footer_key()
+                    .is_some_and(|key| matches!(key, "BREAKING CHANGE" | "BREAKING-CHANGE"))
+            })
+    }
+
+    /// What the commit reverts, for both `revert: <header>` and git's `Revert "<header>"`
+    pub fn revert(&self) -> Option<Revert> {
+        Revert::from_message(self)
+    }
+
+    /// Issue references in the description, body and footers, in message order
+    pub fn references(&self) -> Vec<IssueReference> {
+        self.description
+            .iter()
+            .chain(self.body.iter())
+            .chain(self.footers.iter().flatten())
+            .flat_map(IssueReference::find_in)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum TextCase {
     Camel,
+    /// Kebab case starting with a capital letter, e.g. git trailers like `Signed-off-by`
+    CapitalizedKebab,
     Kebab,
     Lower,
     Pascal,
@@ -20,3 +125,33 @@ pub enum TextCase {
     Upper,
     Unknown,
 }
+
+impl TextCase {
+    /// Single words fit several multi word cases, e.g. `refs` is both lower and kebab case
+    pub fn fits(&self, expected: TextCase) -> bool {
+        match (self, expected) {
+            (actual, expected) if *actual == expected => true,
+            (TextCase::Lower, TextCase::Kebab | TextCase::Snake | TextCase::Camel) => true,
+            (TextCase::Upper, TextCase::Scream) => true,
+            (TextCase::Pascal, TextCase::CapitalizedKebab) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for TextCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            TextCase::Camel => "camelCase",
+            TextCase::CapitalizedKebab => "Capitalized-kebab-case",
+            TextCase::Kebab => "kebab-case",
+            TextCase::Lower => "lowercase",
+            TextCase::Pascal => "PascalCase",
+            TextCase::Scream => "SCREAMING_SNAKE_CASE",
+            TextCase::Snake => "snake_case",
+            TextCase::Upper => "UPPERCASE",
+            TextCase::Unknown => "unknown case",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,104 @@
+use std::{fmt::Display, ops::Range, sync::OnceLock};
+
+use regex::Regex;
+
+use crate::parser_lib::parsing::types::Symbol;
+
+// URLs come first so that `#12` or `PROJ-4` inside a link aren't found on their own.
+// Trailing punctuation is left out of URLs, e.g. the period ending a sentence.
+const REFERENCE_PATTERN: &str = r#"(?x)
+    (?P<url>https?://[^\s<>()"']*[^\s<>()"'.,;:!?])
+    | \B\#(?P<number>\d+)\b
+    | \b(?P<project>[A-Z][A-Z0-9]+)-(?P<key_number>\d+)\b
+"#;
+
+// Standards, hashes and encodings look like issue keys, e.g. SHA-256, UTF-8 or ISO-8601.
+// Trailing digits are ignored, so UTF8-3 is left out as well.
+const NOT_PROJECTS: [&str; 22] = [
+    "AES", "ASCII", "CVE", "ECMA", "ES", "GPL", "HTTP", "IEC", "IEEE", "IPV", "ISO", "LGPL", "MD",
+    "PEP", "RFC", "RSA", "SHA", "SSL", "TLS", "UCS", "UTF", "WCAG",
+];
+
+fn is_project(project: &str) -> bool {
+    !NOT_PROJECTS.contains(&project.trim_end_matches(|c: char| c.is_ascii_digit()))
+}
+
+fn reference_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(REFERENCE_PATTERN).unwrap())
+}
+
+/// A reference to an issue or ticket in an issue tracker
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum IssueReference {
+    /// `#123` as used by GitHub and GitLab
+    Number { number: u64, span: Range<usize> },
+    /// `PROJ-456` as used by Jira, or `GH-7`
+    Key {
+        project: String,
+        number: u64,
+        span: Range<usize>,
+    },
+    /// Full link to an issue
+    Url { url: String, span: Range<usize> },
+}
+
+impl IssueReference {
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            IssueReference::Number { span, .. }
+            | IssueReference::Key { span, .. }
+            | IssueReference::Url { span, .. } => span.clone(),
+        }
+    }
+
+    /// Finds the references in the content of a symbol, spans point into the whole message
+    pub fn find_in(symbol: &Symbol) -> Vec<IssueReference> {
+        if symbol.get_content_tokens().is_empty() {
+            return Vec::new();
+        }
+        let offset = symbol.content_span().start;
+        let text = symbol.content_string();
+        reference_regex()
+            .captures_iter(&text)
+            .filter_map(|captures| {
+                let whole = captures.get(0)?;
+                let span = offset + whole.start()..offset + whole.end();
+                if let Some(url) = captures.name("url") {
+                    return Some(IssueReference::Url {
+                        url: url.as_str().to_string(),
+                        span,
+                    });
+                }
+                if let Some(number) = captures.name("number") {
+                    return Some(IssueReference::Number {
+                        number: number.as_str().parse().ok()?,
+                        span,
+                    });
+                }
+                let project = &captures["project"];
+                if !is_project(project) {
+                    return None;
+                }
+                Some(IssueReference::Key {
+                    project: project.to_string(),
+                    number: captures["key_number"].parse().ok()?,
+                    span,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Display for IssueReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueReference::Number { number, .. } => write!(f, "#{}", number),
+            IssueReference::Key {
+                project, number, ..
+            } => write!(f, "{}-{}", project, number),
+            IssueReference::Url { url, .. } => write!(f, "{}", url),
+        }
+    }
+}
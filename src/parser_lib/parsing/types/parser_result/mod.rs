@@ -1,3 +1,7 @@
 mod commit_message;
+mod issue_reference;
+mod revert;
 
 pub use commit_message::*;
+pub use issue_reference::IssueReference;
+pub use revert::{cancel_reverts, Revert};
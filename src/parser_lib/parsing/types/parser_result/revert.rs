@@ -0,0 +1,228 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use regex::Regex;
+
+use super::CommitMessage;
+
+fn reverts_commit_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"(?i)this reverts commit ([0-9a-f]{7,40})\b").unwrap())
+}
+
+fn is_sha(value: &str) -> bool {
+    (7..=40).contains(&value.len()) && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// What a revert commit reverts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revert {
+    pub(crate) header: String,
+    pub(crate) commits: Vec<String>,
+    pub(crate) generated_by_git: bool,
+}
+
+impl Revert {
+    /// Header of the reverted commit
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// SHAs from `This reverts commit <sha>.` lines and `Refs` footers, possibly abbreviated
+    pub fn commits(&self) -> &[String] {
+        &self.commits
+    }
+
+    /// Written by `git revert` as `Revert "<header>"` instead of `revert: <header>`
+    pub fn is_generated_by_git(&self) -> bool {
+        self.generated_by_git
+    }
+
+    pub fn from_message(message: &CommitMessage) -> Option<Revert> {
+        let topic = message.topic.as_ref()?.content_string();
+        let description = message.description.as_ref()?.content_string();
+        let (header, generated_by_git) = match topic.as_str() {
+            "revert" => (description, false),
+            "Revert" if description.len() > 1 => {
+                let quoted = description.strip_prefix('"')?.strip_suffix('"')?;
+                (quoted.to_string(), true)
+            }
+            _ => return None,
+        };
+
+        let mut commits: Vec<String> = Vec::new();
+        if let Some(body) = &message.body {
+            let body = body.content_string();
+            for captures in reverts_commit_regex().captures_iter(&body) {
+                commits.push(captures[1].to_string());
+            }
+        }
+        for footer in message.footers.iter().flatten() {
+            let (Some(key), Some(value)) = (footer.footer_key(), footer.footer_value()) else {
+                continue;
+            };
+            if key.eq_ignore_ascii_case("refs") {
+                commits.extend(
+                    value
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|sha| is_sha(sha))
+                        .map(|sha| sha.to_string()),
+                );
+            }
+        }
+
+        Some(Revert {
+            header,
+            commits,
+            generated_by_git,
+        })
+    }
+
+    /// Whether the commit with the given SHA and message is the one this reverts.
+    /// Falls back to comparing headers when the revert doesn't name any commit.
+    pub fn reverts(&self, sha: &str, message: &CommitMessage) -> bool {
+        if self.commits.is_empty() {
+            return message.header() == self.header;
+        }
+        // An empty or too short SHA would be a prefix of every commit
+        is_sha(sha)
+            && self.commits.iter().any(|reverted| {
+                is_sha(reverted)
+                    && (sha.starts_with(reverted.as_str()) || reverted.starts_with(sha))
+            })
+    }
+}
+
+/// Drops reverts together with the commits they revert, e.g. before building a changelog
+/// or computing the next version. `commits` are `(sha, message)` pairs, oldest first.
+/// Reverts of commits outside the list are kept, reverting a revert brings the original back.
+pub fn cancel_reverts(commits: Vec<(String, CommitMessage)>) -> Vec<(String, CommitMessage)> {
+    let mut kept: Vec<bool> = vec![true; commits.len()];
+    // Index of a revert to the index of the commit it cancelled
+    let mut cancelled: HashMap<usize, usize> = HashMap::new();
+
+    for (i, (_, message)) in commits.iter().enumerate() {
+        let Some(revert) = message.revert() else {
+            continue;
+        };
+        let target = (0..i)
+            .rev()
+            .find(|&j| revert.reverts(&commits[j].0, &commits[j].1));
+        match target {
+            Some(j) if kept[j] => {
+                kept[i] = false;
+                kept[j] = false;
+                cancelled.insert(i, j);
+            }
+            // Reverting a revert that cancelled a commit restores that commit
+            Some(j) => {
+                if let Some(&original) = cancelled.get(&j) {
+                    kept[i] = false;
+                    kept[original] = true;
+                }
+            }
+            None => (),
+        }
+    }
+
+    commits
+        .into_iter()
+        .zip(kept)
+        .filter_map(|(commit, kept)| kept.then_some(commit))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser_lib::test_utils::parse_message;
+
+    fn commit(sha: &str, message: &str) -> (String, CommitMessage<'static>) {
+        (sha.to_string(), parse_message(message))
+    }
+
+    #[test]
+    fn should_read_git_revert() {
+        let message = parse_message(
+            "Revert \"feat(api): add users\"\n\n\
+             This reverts commit 3f2a1b9c0d4e5f60718293a4b5c6d7e8f9012345.",
+        );
+        assert_eq!(
+            message.revert(),
+            Some(Revert {
+                header: "feat(api): add users".to_string(),
+                commits: vec!["3f2a1b9c0d4e5f60718293a4b5c6d7e8f9012345".to_string()],
+                generated_by_git: true,
+            })
+        );
+    }
+
+    #[test]
+    fn should_read_conventional_revert() {
+        let message = parse_message(
+            "revert: let us never again speak of the noodle incident\n\nRefs: 676104e, a215868",
+        );
+        let revert = message.revert().unwrap();
+        assert_eq!(
+            revert.header,
+            "let us never again speak of the noodle incident"
+        );
+        assert_eq!(revert.commits, ["676104e", "a215868"]);
+        assert!(!revert.generated_by_git);
+        assert!(parse_message("feat: add users").revert().is_none());
+    }
+
+    #[test]
+    fn should_cancel_reverted_commits() {
+        let commits = vec![
+            commit("aaaaaaa1", "feat: add users"),
+            commit("bbbbbbb2", "fix: handle empty input"),
+            commit(
+                "ccccccc3",
+                "Revert \"feat: add users\"\n\nThis reverts commit aaaaaaa1.",
+            ),
+            commit("ddddddd4", "revert: fix: handle empty input"),
+            commit("eeeeeee5", "revert: feat: not in this range"),
+        ];
+        let kept: Vec<String> = cancel_reverts(commits)
+            .into_iter()
+            .map(|(sha, _)| sha)
+            .collect();
+        assert_eq!(kept, ["eeeeeee5"]);
+    }
+
+    #[test]
+    fn should_only_match_commits_by_full_sha_prefixes() {
+        let revert = parse_message(
+            "Revert \"feat: add users\"\n\n\
+             This reverts commit 3f2a1b9c0d4e5f60718293a4b5c6d7e8f9012345.",
+        )
+        .revert()
+        .unwrap();
+        let reverted = parse_message("feat: add users");
+        assert!(revert.reverts("3f2a1b9", &reverted));
+        assert!(revert.reverts("3f2a1b9c0d4e5f60718293a4b5c6d7e8f9012345", &reverted));
+        assert!(!revert.reverts("", &reverted));
+        assert!(!revert.reverts("3f2a1b", &reverted));
+        assert!(!revert.reverts("3f2a1bz", &reverted));
+    }
+
+    #[test]
+    fn should_restore_commit_when_revert_is_reverted() {
+        let commits = vec![
+            commit("aaaaaaa1", "feat: add users"),
+            commit(
+                "bbbbbbb2",
+                "Revert \"feat: add users\"\n\nThis reverts commit aaaaaaa1.",
+            ),
+            commit(
+                "ccccccc3",
+                "Revert \"Revert \"feat: add users\"\"\n\nThis reverts commit bbbbbbb2.",
+            ),
+        ];
+        let kept: Vec<String> = cancel_reverts(commits)
+            .into_iter()
+            .map(|(sha, _)| sha)
+            .collect();
+        assert_eq!(kept, ["aaaaaaa1"]);
+    }
+}
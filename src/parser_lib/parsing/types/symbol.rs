@@ -1,66 +1,81 @@
-use crate::parser_lib::{lexing::types::Token, SlicableRcString};
+use std::ops::Range;
 
-trait SRcStringFromTokens {
-    fn to_srcs(&self) -> SlicableRcString;
+use crate::parser_lib::{lexing::types::Token, text_length::LengthUnit, SlicableRcString};
+
+trait SRcStringFromTokens<'a> {
+    fn to_srcs(&self) -> Option<SlicableRcString<'a>>;
 }
 
-impl SRcStringFromTokens for Vec<&Token> {
-    fn to_srcs(&self) -> SlicableRcString {
-        let first = self.first().unwrap();
-        let last = self.last().unwrap();
-        first.get_super_slice(first.get_start_index()..last.get_end_index())
+impl<'a> SRcStringFromTokens<'a> for Vec<&Token<'a>> {
+    // `None` for an empty list, there's nothing to slice the source with
+    fn to_srcs(&self) -> Option<SlicableRcString<'a>> {
+        let first = self.first()?;
+        let last = self.last()?;
+        Some(first.get_super_slice(first.get_start_index()..last.get_end_index()))
     }
 }
 
+/// A part of a parsed message, e.g. the topic or a footer, with the tokens it's made of
 #[derive(Debug, Clone, PartialEq)]
-pub struct FooterData {
-    pub key: Token,
-    pub value: Token,
+pub struct Symbol<'a> {
+    kind: SymbolKind<'a>,
+}
+
+impl<'a> From<SymbolKind<'a>> for Symbol<'a> {
+    fn from(kind: SymbolKind<'a>) -> Self {
+        Self { kind }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum Symbol {
+pub(crate) enum SymbolKind<'a> {
     // Use named fields instead of a tuple to make adding stuff later easier
     // e.g. word casing and delimiters
     Topic {
-        text_token: Token,
+        text_token: Token<'a>,
     },
     Scope {
-        text_token: Token,
-        start_delimiter: Token,
-        end_delimiter: Token,
+        text_token: Token<'a>,
+        start_delimiter: Token<'a>,
+        end_delimiter: Token<'a>,
     },
     Description {
-        text_tokens: Vec<Token>,
-        start_delimiter: Vec<Token>,
-        braking_change_token: Option<Token>,
+        text_tokens: Vec<Token<'a>>,
+        start_delimiter: Vec<Token<'a>>,
+        braking_change_token: Option<Token<'a>>,
     },
     Body {
-        start_delimiter: Vec<Token>,
-        text_tokens: Vec<Token>,
+        start_delimiter: Vec<Token<'a>>,
+        text_tokens: Vec<Token<'a>>,
+        // Newlines between the body and the footers
+        end_delimiter: Vec<Token<'a>>,
     },
     Footer {
         // Delimiter is either 'word: ' or 'word #word'
-        start_delimiter: Vec<Token>,
-        text_tokens: Vec<Token>,
+        start_delimiter: Vec<Token<'a>>,
+        text_tokens: Vec<Token<'a>>,
     },
 }
 
-impl Symbol {
-    pub fn get_content_tokens(&self) -> Vec<&Token> {
-        match self {
-            Symbol::Topic { text_token, .. } => vec![text_token],
-            Symbol::Scope { text_token, .. } => vec![text_token],
-            Symbol::Description { text_tokens, .. } => text_tokens.iter().collect(),
-            Symbol::Body {
+impl<'a> Symbol<'a> {
+    pub(crate) fn kind(&self) -> &SymbolKind<'a> {
+        &self.kind
+    }
+
+    pub(crate) fn get_content_tokens(&self) -> Vec<&Token<'a>> {
+        match &self.kind {
+            SymbolKind::Topic { text_token, .. } => vec![text_token],
+            SymbolKind::Scope { text_token, .. } => vec![text_token],
+            SymbolKind::Description { text_tokens, .. } => text_tokens.iter().collect(),
+            SymbolKind::Body {
                 text_tokens: tokens,
                 ..
             } => tokens.iter().collect(),
-            Symbol::Footer {
+            SymbolKind::Footer {
                 start_delimiter: key,
                 text_tokens,
             } => {
-                let mut tokens: Vec<&Token> = Vec::new();
+                let mut tokens: Vec<&Token<'a>> = Vec::new();
                 tokens.extend(key);
                 tokens.extend(text_tokens);
                 tokens
@@ -68,19 +83,19 @@ impl Symbol {
         }
     }
 
-    pub fn get_all_tokens(&self) -> Vec<&Token> {
-        match self {
-            Symbol::Topic { text_token } => {
+    pub(crate) fn get_all_tokens(&self) -> Vec<&Token<'a>> {
+        match &self.kind {
+            SymbolKind::Topic { text_token } => {
                 vec![text_token]
             }
 
-            Symbol::Scope {
+            SymbolKind::Scope {
                 text_token,
                 end_delimiter,
                 start_delimiter,
             } => vec![start_delimiter, text_token, end_delimiter],
 
-            Symbol::Description {
+            SymbolKind::Description {
                 text_tokens,
                 start_delimiter,
                 braking_change_token,
@@ -93,21 +108,23 @@ impl Symbol {
                 tokens.extend(text_tokens);
                 tokens
             }
-            Symbol::Body {
+            SymbolKind::Body {
                 start_delimiter,
                 text_tokens,
+                end_delimiter,
             } => {
-                let mut tokens: Vec<&Token> = Vec::new();
+                let mut tokens: Vec<&Token<'a>> = Vec::new();
                 tokens.extend(start_delimiter);
                 tokens.extend(text_tokens);
+                tokens.extend(end_delimiter);
                 tokens
             }
 
-            Symbol::Footer {
+            SymbolKind::Footer {
                 start_delimiter: key,
                 text_tokens: value,
             } => {
-                let mut tokens: Vec<&Token> = Vec::new();
+                let mut tokens: Vec<&Token<'a>> = Vec::new();
                 tokens.extend(key);
                 tokens.extend(value);
                 tokens
@@ -115,67 +132,158 @@ impl Symbol {
         }
     }
     pub fn full_string(&self) -> String {
-        self.get_all_tokens().to_srcs().to_string()
+        self.get_all_tokens()
+            .to_srcs()
+            .map(|srcs| srcs.to_string())
+            .unwrap_or_default()
     }
 
     pub fn content_string(&self) -> String {
-        self.get_content_tokens().to_srcs().to_string()
+        self.get_content_tokens()
+            .to_srcs()
+            .map(|srcs| srcs.to_string())
+            .unwrap_or_default()
+    }
+
+    /// The content without allocating, empty when the symbol has no content tokens
+    pub fn content_str(&self) -> &str {
+        self.slice(self.content_span())
+    }
+
+    /// The symbol including delimiters without allocating
+    pub fn full_str(&self) -> &str {
+        self.slice(self.span())
+    }
+
+    fn slice(&self, span: Range<usize>) -> &str {
+        match self.get_all_tokens().first() {
+            Some(token) => &token.get_source()[span],
+            None => "",
+        }
     }
 
     pub fn content_length(&self) -> usize {
-        return self
-            .get_content_tokens()
+        self.get_content_tokens()
             .iter()
             .map(|t| t.get_length())
-            .sum();
+            .sum()
+    }
+
+    /// Length of the content as readers count it, `content_length` is in bytes
+    pub fn content_length_in(&self, unit: LengthUnit) -> usize {
+        unit.measure(&self.content_string())
     }
 
     pub fn total_length(&self) -> usize {
-        return self.get_all_tokens().iter().map(|t| t.get_length()).sum();
+        self.get_all_tokens().iter().map(|t| t.get_length()).sum()
+    }
+
+    /// Start of the first token, `None` when the symbol has no tokens
+    pub fn start_i(&self) -> Option<usize> {
+        self.get_all_tokens()
+            .first()
+            .map(|token| token.get_start_index())
+    }
+
+    /// End of the last token, `None` when the symbol has no tokens
+    pub fn end_i(&self) -> Option<usize> {
+        self.get_all_tokens()
+            .last()
+            .map(|token| token.get_end_index())
     }
 
-    pub fn start_i(&self) -> usize {
-        return self.get_all_tokens().first().unwrap().get_start_index();
+    /// Span of all tokens including delimiters
+    pub fn span(&self) -> Range<usize> {
+        let start = self.start_i().unwrap_or_default();
+        start..self.end_i().unwrap_or(start)
     }
 
-    pub fn end_i(&self) -> usize {
-        return self.get_all_tokens().last().unwrap().get_end_index();
+    pub fn content_span(&self) -> Range<usize> {
+        let tokens = self.get_content_tokens();
+        match (tokens.first(), tokens.last()) {
+            (Some(first), Some(last)) => first.get_start_index()..last.get_end_index(),
+            _ => self.span().start..self.span().start,
+        }
+    }
+
+    /// Key of a footer, e.g. `Refs` for both `Refs: #1` and `Refs #1`, or `BREAKING CHANGE`
+    pub fn footer_key(&self) -> Option<&str> {
+        let span = self.footer_key_span()?;
+        Some(self.slice(span))
+    }
+
+    pub fn footer_key_span(&self) -> Option<Range<usize>> {
+        match &self.kind {
+            SymbolKind::Footer {
+                start_delimiter, ..
+            } => {
+                // Everything before the two separator tokens, `: ` or ` #`
+                let first = start_delimiter.first()?;
+                let last = start_delimiter.iter().rev().nth(2)?;
+                Some(first.get_start_index()..last.get_end_index())
+            }
+            _ => None,
+        }
+    }
+
+    /// Value of a footer without trailing newlines. The hash of `word #word` footers is kept.
+    pub fn footer_value(&self) -> Option<String> {
+        match &self.kind {
+            SymbolKind::Footer {
+                start_delimiter,
+                text_tokens,
+            } => {
+                let hash = match start_delimiter.last() {
+                    Some(Token::Hash(_)) => "#",
+                    _ => "",
+                };
+                let text: String = text_tokens.iter().map(|t| t.get_value()).collect();
+                Some(format!("{}{}", hash, text.trim_end_matches('\n')))
+            }
+            _ => None,
+        }
     }
 
     pub fn end_delimiter(&self) -> Option<String> {
-        match self {
-            Symbol::Scope { end_delimiter, .. } => Some(end_delimiter.get_value()),
+        match &self.kind {
+            SymbolKind::Scope { end_delimiter, .. } => Some(end_delimiter.get_value()),
+            SymbolKind::Body { end_delimiter, .. } => Some(
+                end_delimiter
+                    .iter()
+                    .map(|t| t.get_value())
+                    .collect::<String>(),
+            ),
             _ => None,
         }
     }
 
     pub fn start_delimiter(&self) -> Option<String> {
-        match self {
-            Symbol::Scope {
+        match &self.kind {
+            SymbolKind::Scope {
                 start_delimiter, ..
             } => Some(start_delimiter.get_value()),
-            Symbol::Description {
+            SymbolKind::Description {
                 start_delimiter, ..
             } => Some(
                 start_delimiter
-                    .iter
-                    .map(|t| t.to_string())
+                    .iter()
+                    .map(|t| t.get_value())
                     .collect::<String>(),
             ),
-            Symbol::Body {
+            SymbolKind::Body {
                 start_delimiter, ..
             } => Some(
                 start_delimiter
-                    .iter
-                    .map(|t| t.to_string())
+                    .iter()
+                    .map(|t| t.get_value())
                     .collect::<String>(),
             ),
-            Symbol::Footer {
+            SymbolKind::Footer {
                 start_delimiter, ..
             } => Some(
                 start_delimiter
-                    .iter
-                    .map(|t| t.to_string())
+                    .iter()
+                    .map(|t| t.get_value())
                     .collect::<String>(),
             ),
             _ => None,
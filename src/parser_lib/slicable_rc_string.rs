@@ -4,21 +4,64 @@ use std::{
     rc::Rc,
 };
 
+// The message is shared when the parser owns it and borrowed when parsing a `&str`
+#[derive(Clone, Eq, Hash, PartialEq)]
+enum Source<'a> {
+    Shared(Rc<String>),
+    Borrowed(&'a str),
+}
+
+impl Deref for Source<'_> {
+    type Target = str;
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Source::Shared(string) => string,
+            Source::Borrowed(string) => string,
+        }
+    }
+}
+
+/// A slice of a commit message that's cheap to clone, the message is either shared or borrowed
 #[derive(Eq, Hash, PartialEq)]
-pub struct SlicableRcString {
-    string: Rc<String>,
+pub(crate) struct SlicableRcString<'a> {
+    string: Source<'a>,
     span: Range<usize>,
 }
 
-impl SlicableRcString {
+impl SlicableRcString<'static> {
     pub fn new(string: Rc<String>) -> Self {
         let span = 0..string.len();
-        Self { string, span }
+        Self {
+            string: Source::Shared(string),
+            span,
+        }
+    }
+}
+
+impl<'a> SlicableRcString<'a> {
+    /// Slices `string` without copying it
+    pub fn borrowed(string: &'a str) -> Self {
+        Self {
+            string: Source::Borrowed(string),
+            span: 0..string.len(),
+        }
+    }
+
+    /// Copies a borrowed message so the slice can outlive it
+    pub fn into_owned(self) -> SlicableRcString<'static> {
+        let string = match self.string {
+            Source::Shared(string) => string,
+            Source::Borrowed(string) => Rc::new(string.to_string()),
+        };
+        SlicableRcString {
+            string: Source::Shared(string),
+            span: self.span,
+        }
     }
 
     pub fn substr(&self, range: Range<usize>) -> Self {
         Self {
-            string: Rc::clone(&self.string),
+            string: self.string.clone(),
             span: (self.span.start + range.start)..(self.span.start + range.end),
         }
     }
@@ -35,58 +78,64 @@ impl SlicableRcString {
         self.span.end - self.span.start
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    pub fn value(&self) -> String {
+        self.as_str().to_string()
     }
 
-    pub fn value(&self) -> String {
-        self.string[self.span.clone()].to_string()
+    /// The slice without allocating, unlike `value`
+    pub fn as_str(&self) -> &str {
+        &self.string[self.span.clone()]
+    }
+
+    /// The full referenced string
+    pub fn source(&self) -> &str {
+        &self.string
     }
 
     /// ## Get a slice of the full rerefenced string
     pub fn super_slice(&self, span: Range<usize>) -> Self {
         Self {
-            string: Rc::clone(&self.string),
+            string: self.string.clone(),
             span,
         }
     }
 }
 
-impl Deref for SlicableRcString {
+impl Deref for SlicableRcString<'_> {
     type Target = str;
     fn deref(&self) -> &Self::Target {
-        &self.string[self.span.clone()]
+        self.as_str()
     }
 }
 
-impl Clone for SlicableRcString {
+impl Clone for SlicableRcString<'_> {
     fn clone(&self) -> Self {
         Self {
-            string: Rc::clone(&self.string),
+            string: self.string.clone(),
             span: self.span.clone(),
         }
     }
 }
 
-impl Display for SlicableRcString {
+impl Display for SlicableRcString<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.value())
+        write!(f, "{}", self.as_str())
     }
 }
 
-impl Debug for SlicableRcString {
+impl Debug for SlicableRcString<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
             "SRS {{ slice(): \"{0}\", span: {1:?} }}",
-            self.value(),
+            self.as_str(),
             self.span
         )
     }
 }
 
-impl PartialEq<&str> for SlicableRcString {
+impl PartialEq<&str> for SlicableRcString<'_> {
     fn eq(&self, other: &&str) -> bool {
-        self.value() == *other
+        self.as_str() == *other
     }
 }
@@ -0,0 +1,316 @@
+mod table;
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use super::{git::LogEntry, CommitMessageParser, Processed};
+
+/// Rule id under which messages that don't parse are counted
+const SYNTAX_ERROR_RULE: &str = "syntax-error";
+
+const UNRELEASED: &str = "unreleased";
+
+/// How many commits pass validation
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct Conformance {
+    pub commits: usize,
+    pub conforming: usize,
+    /// Percentage of conforming commits
+    pub rate: f64,
+}
+
+impl Conformance {
+    fn add(&mut self, conforming: bool) {
+        self.commits += 1;
+        self.conforming += usize::from(conforming);
+        self.rate = self.conforming as f64 * 100.0 / self.commits as f64;
+    }
+}
+
+/// Commits up to and including a tagged commit, except those the ignore rules skip
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct Release {
+    pub name: String,
+    pub commits: usize,
+    pub breaking: usize,
+}
+
+/// Conformance report over a range of commits, shown as tables or serialized to JSON
+#[derive(Debug, Default, Serialize)]
+#[non_exhaustive]
+pub struct Stats {
+    /// Commits the ignore rules skip, e.g. merges. They aren't in any other count.
+    pub ignored: usize,
+    pub overall: Conformance,
+    pub by_month: BTreeMap<String, Conformance>,
+    pub by_author: BTreeMap<String, Conformance>,
+    pub topics: BTreeMap<String, usize>,
+    pub scopes: BTreeMap<String, usize>,
+    /// Failures by rule id, messages that don't parse count as `syntax-error`
+    pub violations: BTreeMap<String, usize>,
+    /// Oldest first, commits after the last tag are `unreleased`
+    pub releases: Vec<Release>,
+    /// In the unit of the description length limit
+    pub average_description_length: f64,
+}
+
+impl Stats {
+    /// Lints every commit with the parser's rules. `entries` are newest first, as `read_log` lists them.
+    pub fn collect(entries: &[LogEntry], parser: &mut CommitMessageParser) -> Stats {
+        let mut stats = Stats::default();
+        let release_of = Self::releases_of(entries, &mut stats.releases);
+        let mut unreleased = Release {
+            name: UNRELEASED.to_string(),
+            ..Release::default()
+        };
+        let mut description_lengths = Vec::new();
+        for (entry, release) in entries.iter().zip(release_of).rev() {
+            let Some(breaking) = stats.add(entry, parser, &mut description_lengths) else {
+                continue;
+            };
+            let release = match release {
+                Some(i) => &mut stats.releases[i],
+                None => &mut unreleased,
+            };
+            release.commits += 1;
+            release.breaking += usize::from(breaking);
+        }
+        if unreleased.commits > 0 {
+            stats.releases.push(unreleased);
+        }
+        if !description_lengths.is_empty() {
+            stats.average_description_length =
+                description_lengths.iter().sum::<usize>() as f64 / description_lengths.len() as f64;
+        }
+        stats
+    }
+
+    // A commit belongs to the oldest tag it's an ancestor of, like `git log prevtag..tag` shows it.
+    // Following parents keeps commits of merged branches out of tags made before the merge.
+    // Pushes the tags oldest first and returns the index of the release of each entry.
+    fn releases_of(entries: &[LogEntry], releases: &mut Vec<Release>) -> Vec<Option<usize>> {
+        let index: HashMap<&str, usize> = entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (entry.hash.as_str(), i))
+            .collect();
+        let mut release_of = vec![None; entries.len()];
+        for (i, entry) in entries.iter().enumerate().rev() {
+            let Some(tag) = entry.tags.first() else {
+                continue;
+            };
+            let release = releases.len();
+            releases.push(Release {
+                name: tag.clone(),
+                ..Release::default()
+            });
+            let mut pending = vec![i];
+            while let Some(i) = pending.pop() {
+                if release_of[i].is_some() {
+                    continue;
+                }
+                release_of[i] = Some(release);
+                pending.extend(
+                    entries[i]
+                        .parents
+                        .iter()
+                        .filter_map(|parent| index.get(parent.as_str())),
+                );
+            }
+        }
+        release_of
+    }
+
+    // Returns whether the commit is a breaking change, `None` when the ignore rules skip it
+    fn add(
+        &mut self,
+        entry: &LogEntry,
+        parser: &mut CommitMessageParser,
+        description_lengths: &mut Vec<usize>,
+    ) -> Option<bool> {
+        let (conforming, breaking) = match parser.check(&entry.message) {
+            Ok(Processed::Ignored) => {
+                self.ignored += 1;
+                return None;
+            }
+            Ok(Processed::Checked(parsed, errors)) => {
+                if let Some(topic) = parsed.topic() {
+                    *self.topics.entry(topic.content_string()).or_default() += 1;
+                }
+                if let Some(scope) = parsed.scope() {
+                    *self.scopes.entry(scope.content_string()).or_default() += 1;
+                }
+                if let Some(description) = parsed.description() {
+                    let unit = parser.rules().length_unit();
+                    description_lengths.push(description.content_length_in(unit));
+                }
+                for error in errors.iter() {
+                    *self
+                        .violations
+                        .entry(error.rule_id().to_string())
+                        .or_default() += 1;
+                }
+                (errors.is_empty(), parsed.is_breaking())
+            }
+            Err(_) => {
+                *self
+                    .violations
+                    .entry(SYNTAX_ERROR_RULE.to_string())
+                    .or_default() += 1;
+                (false, false)
+            }
+        };
+
+        self.overall.add(conforming);
+        self.by_month
+            .entry(entry.month.clone())
+            .or_default()
+            .add(conforming);
+        self.by_author
+            .entry(entry.author.clone())
+            .or_default()
+            .add(conforming);
+        Some(breaking)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, process::Command};
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::parser_lib::git::read_log;
+
+    fn entry(author: &str, month: &str, tags: &[&str], message: &str) -> LogEntry {
+        LogEntry {
+            hash: String::new(),
+            parents: Vec::new(),
+            author: author.to_string(),
+            month: month.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            message: message.to_string(),
+        }
+    }
+
+    // Newest first, each commit the parent of the one before it
+    fn log() -> Vec<LogEntry> {
+        let mut entries = vec![
+            entry("Bob", "2024-05", &[], "fix: late fix"),
+            entry("Ada", "2024-05", &["v2.0"], "feat(api)!: drop v1"),
+            entry("Bob", "2024-04", &[], "Merge branch 'main'"),
+            entry("Bob", "2024-04", &[], "fature: typo"),
+            entry("Ada", "2024-04", &["v1.0"], "feat(api): add users"),
+            entry("Bob", "2024-03", &[], "added stuff"),
+        ];
+        let count = entries.len();
+        for (i, entry) in entries.iter_mut().enumerate() {
+            entry.hash = i.to_string();
+            entry.parents = (i + 1..count).take(1).map(|p| p.to_string()).collect();
+        }
+        entries
+    }
+
+    fn git(dir: &Path, args: &[&str], date: &str) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Ada", "-c", "user.email=ada@example.com"])
+            .args(args)
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn should_count_conformance() {
+        let stats = Stats::collect(&log(), &mut CommitMessageParser::new());
+        assert_eq!(stats.ignored, 1);
+        assert_eq!(stats.overall.commits, 5);
+        assert_eq!(stats.overall.conforming, 3);
+        assert_eq!(stats.by_month["2024-03"].rate, 0.0);
+        assert_eq!(stats.by_month["2024-04"].commits, 2);
+        assert_eq!(stats.by_month["2024-05"].rate, 100.0);
+        assert_eq!(stats.by_author["Ada"].conforming, 2);
+        assert_eq!(stats.by_author["Bob"].conforming, 1);
+    }
+
+    #[test]
+    fn should_count_topics_scopes_and_violations() {
+        let stats = Stats::collect(&log(), &mut CommitMessageParser::new());
+        assert_eq!(stats.topics["feat"], 2);
+        assert_eq!(stats.topics["fature"], 1);
+        assert_eq!(stats.scopes["api"], 2);
+        assert_eq!(stats.violations["unknown-topic"], 1);
+        assert_eq!(stats.violations[SYNTAX_ERROR_RULE], 1);
+        // "add users", "typo", "drop v1" and "late fix"
+        assert_eq!(stats.average_description_length, 7.0);
+    }
+
+    #[test]
+    fn should_group_breaking_changes_by_release() {
+        let stats = Stats::collect(&log(), &mut CommitMessageParser::new());
+        let releases: Vec<(&str, usize, usize)> = stats
+            .releases
+            .iter()
+            .map(|release| (release.name.as_str(), release.commits, release.breaking))
+            .collect();
+        assert_eq!(
+            releases,
+            // The merge before v2.0 is ignored
+            [("v1.0", 2, 0), ("v2.0", 2, 1), (UNRELEASED, 1, 0)]
+        );
+    }
+
+    #[test]
+    fn should_count_merged_commits_under_the_release_they_were_merged_in() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let commit = |message: &str, date: &str| {
+            git(
+                path,
+                &["commit", "-q", "--allow-empty", "-m", message],
+                date,
+            )
+        };
+        git(path, &["init", "-q", "-b", "main"], "2024-01-01T00:00:00");
+        commit("feat: first", "2024-01-01T00:00:00");
+        git(
+            path,
+            &["checkout", "-q", "-b", "topic"],
+            "2024-01-01T00:00:00",
+        );
+        // Older than v1.0, but only merged after it
+        commit("feat!: from topic", "2024-01-02T00:00:00");
+        git(path, &["checkout", "-q", "main"], "2024-01-03T00:00:00");
+        commit("fix: on main", "2024-01-03T00:00:00");
+        git(path, &["tag", "v1.0"], "2024-01-03T00:00:00");
+        let merge = [
+            "merge",
+            "-q",
+            "--no-ff",
+            "-m",
+            "Merge branch 'topic'",
+            "topic",
+        ];
+        git(path, &merge, "2024-01-04T00:00:00");
+        git(path, &["tag", "v2.0"], "2024-01-04T00:00:00");
+        commit("fix: after", "2024-01-05T00:00:00");
+
+        let entries = read_log(path, "HEAD").unwrap();
+        let stats = Stats::collect(&entries, &mut CommitMessageParser::new());
+        let releases: Vec<(&str, usize, usize)> = stats
+            .releases
+            .iter()
+            .map(|release| (release.name.as_str(), release.commits, release.breaking))
+            .collect();
+        assert_eq!(
+            releases,
+            // v2.0 tags the merge, which is ignored
+            [("v1.0", 2, 0), ("v2.0", 1, 1), (UNRELEASED, 1, 0)]
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use itertools::Itertools;
+
+use super::{Conformance, Stats};
+
+// Columns after the first are numbers and aligned right
+fn write_table(f: &mut Formatter<'_>, headers: &[&str], rows: &[Vec<String>]) -> fmt::Result {
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([headers[column].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    for row in [headers.iter().map(|header| header.to_string()).collect()]
+        .iter()
+        .chain(rows)
+    {
+        let cells = row
+            .iter()
+            .zip(widths.iter())
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                let padding = " ".repeat(width - cell.chars().count());
+                if i == 0 {
+                    format!("{}{}", cell, padding)
+                } else {
+                    format!("{}{}", padding, cell)
+                }
+            });
+        writeln!(f, "{}", cells.collect_vec().join("  ").trim_end())?;
+    }
+    writeln!(f)
+}
+
+fn conformance_rows(conformance: &BTreeMap<String, Conformance>) -> Vec<Vec<String>> {
+    conformance
+        .iter()
+        .map(|(key, conformance)| {
+            vec![
+                key.clone(),
+                conformance.commits.to_string(),
+                conformance.conforming.to_string(),
+                format!("{:.1}%", conformance.rate),
+            ]
+        })
+        .collect()
+}
+
+// Most frequent first
+fn count_rows(counts: &BTreeMap<String, usize>) -> Vec<Vec<String>> {
+    counts
+        .iter()
+        .sorted_by_key(|(_, count)| std::cmp::Reverse(**count))
+        .map(|(key, count)| vec![key.clone(), count.to_string()])
+        .collect()
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Conformance: {:.1}% of {} commits ({} ignored)",
+            self.overall.rate, self.overall.commits, self.ignored
+        )?;
+        writeln!(
+            f,
+            "Average description length: {:.1}\n",
+            self.average_description_length
+        )?;
+
+        let conformance_headers = ["Commits", "Conforming", "Rate"];
+        write_table(
+            f,
+            &[&["Month"], &conformance_headers[..]].concat(),
+            &conformance_rows(&self.by_month),
+        )?;
+        write_table(
+            f,
+            &[&["Author"], &conformance_headers[..]].concat(),
+            &conformance_rows(&self.by_author),
+        )?;
+        write_table(f, &["Topic", "Commits"], &count_rows(&self.topics))?;
+        write_table(f, &["Scope", "Commits"], &count_rows(&self.scopes))?;
+        write_table(f, &["Rule", "Violations"], &count_rows(&self.violations))?;
+
+        let releases: Vec<Vec<String>> = self
+            .releases
+            .iter()
+            .map(|release| {
+                vec![
+                    release.name.clone(),
+                    release.commits.to_string(),
+                    release.breaking.to_string(),
+                ]
+            })
+            .collect();
+        write_table(f, &["Release", "Commits", "Breaking"], &releases)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_align_columns() {
+        let mut stats = Stats::default();
+        stats.topics.insert("feat".to_string(), 3);
+        stats.topics.insert("documentation".to_string(), 12);
+        let table = stats.to_string();
+        assert!(table
+            .contains("Topic          Commits\ndocumentation       12\nfeat                 3\n"));
+    }
+}
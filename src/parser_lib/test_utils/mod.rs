@@ -1,9 +1,35 @@
+mod strategies;
 mod test_token_builder;
+pub use strategies::{arb_conventional_message, arb_message};
 pub use test_token_builder::{ExpectedValue, TestStrings, TestTokenBuilder};
 
-use crate::parser_lib::parsing::types::CommitMessage;
+use std::rc::Rc;
+
+use crate::parser_lib::{
+    lexing::Lexer,
+    parsing::{
+        types::{CommitMessage, ParseMode},
+        Parser,
+    },
+    validation::{rules::ValidationRules, try_parse_config},
+};
 
 use super::parsing::types::Symbol;
+
+/// Parses a message strictly, panicking on syntax errors
+pub fn parse_message(message: &str) -> CommitMessage<'static> {
+    Parser::process(
+        Lexer::shared(Rc::new(message.to_string())),
+        ParseMode::Strict,
+    )
+    .unwrap()
+}
+
+/// Reads rules from a config snippet like `rclint.toml` would, panicking on invalid config
+pub fn rules_from(config: &str) -> ValidationRules {
+    try_parse_config(config.to_string()).unwrap()
+}
+
 pub fn assert_commit_message_eq_expected(msg: CommitMessage, expected: TestStrings) {
     assert_part_value_eq_expected(msg.topic, expected.topic, "Topic");
     assert_part_value_eq_expected(msg.scope, expected.scope, "Scope");
@@ -0,0 +1,71 @@
+use proptest::prelude::*;
+
+// Pieces that mean something to the lexer or parser, mixed with text that doesn't
+const FRAGMENTS: [&str; 24] = [
+    "feat",
+    "fix",
+    "api",
+    "Refs",
+    "BREAKING CHANGE",
+    "Revert",
+    "\"",
+    "#12",
+    ":sparkles:",
+    "✨",
+    "café",
+    "👨‍👩‍👧",
+    ":",
+    "(",
+    ")",
+    "!",
+    "#",
+    " ",
+    "  ",
+    "\n",
+    "\n\n",
+    "\t",
+    "\r\n",
+    "-",
+];
+
+/// Any text, biased towards the characters commit messages are tokenized on
+pub fn arb_message() -> impl Strategy<Value = String> {
+    let fragment = prop_oneof![
+        3 => proptest::sample::select(FRAGMENTS.as_slice()).prop_map(str::to_string),
+        1 => any::<String>(),
+    ];
+    proptest::collection::vec(fragment, 0..32).prop_map(|fragments| fragments.concat())
+}
+
+/// Messages shaped like conventional commits, most of them valid
+pub fn arb_conventional_message() -> impl Strategy<Value = String> {
+    let word = "[a-zA-Z0-9é✨-]{1,8}";
+    (
+        word,
+        proptest::option::of(word),
+        any::<bool>(),
+        proptest::collection::vec(word, 1..6),
+        proptest::option::of(proptest::collection::vec(word, 1..10)),
+        proptest::collection::vec((word, word), 0..3),
+    )
+        .prop_map(|(topic, scope, bang, description, body, footers)| {
+            let mut message = topic;
+            if let Some(scope) = scope {
+                message.push_str(&format!("({})", scope));
+            }
+            if bang {
+                message.push('!');
+            }
+            message.push_str(": ");
+            message.push_str(&description.join(" "));
+            if let Some(body) = body {
+                message.push_str("\n\n");
+                message.push_str(&body.join(" "));
+            }
+            for (i, (key, value)) in footers.iter().enumerate() {
+                message.push_str(if i == 0 { "\n\n" } else { "\n" });
+                message.push_str(&format!("{}: {}", key, value));
+            }
+            message
+        })
+}
@@ -59,6 +59,12 @@ impl TestTokenBodyBuilder {
     }
 }
 
+impl Default for TestTokenBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TestTokenBuilder {
     pub fn new() -> TestTokenBuilder {
         TestTokenBuilder {
@@ -95,54 +101,54 @@ impl TestTokenBuilder {
             len,
         });
         self.string.push_str(value);
-        return self;
+        self
     }
 
     pub fn space(&mut self) -> &mut Self {
         self.test_token_buf
             .push(TokenType::Space(self.next_index()));
         self.string.push(' ');
-        return self;
+        self
     }
 
     pub fn hash(&mut self) -> &mut Self {
         self.test_token_buf.push(TokenType::Hash(self.next_index()));
         self.string.push('#');
-        return self;
+        self
     }
 
     pub fn colon(&mut self) -> &mut Self {
         self.test_token_buf
             .push(TokenType::Colon(self.next_index()));
         self.string.push(':');
-        return self;
+        self
     }
 
     pub fn bang(&mut self) -> &mut Self {
         self.test_token_buf.push(TokenType::Bang(self.next_index()));
         self.string.push('!');
-        return self;
+        self
     }
 
     pub fn newline(&mut self) -> &mut Self {
         self.test_token_buf
             .push(TokenType::NewLine(self.next_index()));
         self.string.push('\n');
-        return self;
+        self
     }
 
     pub fn parenthesis_open(&mut self) -> &mut Self {
         self.test_token_buf
             .push(TokenType::ParenthesisOpen(self.next_index()));
         self.string.push('(');
-        return self;
+        self
     }
 
     pub fn parenthesis_close(&mut self) -> &mut Self {
         self.test_token_buf
             .push(TokenType::ParenthesisClose(self.next_index()));
         self.string.push(')');
-        return self;
+        self
     }
 
     pub fn string(&mut self, value: &str) -> &mut Self {
@@ -154,7 +160,7 @@ impl TestTokenBuilder {
             }
         }
 
-        return self;
+        self
     }
 
     /// Alias for word(&mut self, value: &str)
@@ -164,7 +170,7 @@ impl TestTokenBuilder {
             full: value.to_string(),
         });
         self.word(value);
-        return self;
+        self
     }
 
     pub fn scope(&mut self, value: &str) -> &mut Self {
@@ -173,7 +179,7 @@ impl TestTokenBuilder {
             full: format!("({})", value),
         });
         self.parenthesis_open().word(value).parenthesis_close();
-        return self;
+        self
     }
 
     pub fn description(&mut self, value: &str) -> &mut Self {
@@ -182,7 +188,7 @@ impl TestTokenBuilder {
             full: format!(": {}", value),
         });
         self.colon().space().string(value);
-        return self;
+        self
     }
 
     pub fn description_with_bang(&mut self, value: &str) -> &mut Self {
@@ -191,7 +197,7 @@ impl TestTokenBuilder {
             full: format!("!: {}", value),
         });
         self.bang().colon().space().string(value);
-        return self;
+        self
     }
 
     pub fn body(
@@ -202,7 +208,10 @@ impl TestTokenBuilder {
         body_builder.newline().newline();
         build_fn(&mut body_builder);
         self.test_token_buf.extend(body_builder.get_token_buf());
-        let no_delims = body_builder.string.clone().split_off(self.string.len() + 2);
+        // Newlines before the footers aren't part of the body's content
+        let no_delims = body_builder.string[self.string.len() + 2..]
+            .trim_end_matches('\n')
+            .to_string();
         self.body = Some(ExpectedValue {
             no_delims,
             full: format!(
@@ -211,7 +220,7 @@ impl TestTokenBuilder {
             ),
         });
         self.string = body_builder.string;
-        return self;
+        self
     }
 
     pub fn colon_footer(&mut self, input: &str, add_newline: bool) -> &mut Self {
@@ -234,10 +243,10 @@ impl TestTokenBuilder {
         }
 
         self.footers.as_mut().unwrap().push(ExpectedValue {
-            full: format!("{}", value),
-            no_delims: format!("{}", value),
+            full: value.clone(),
+            no_delims: value,
         });
-        return self;
+        self
     }
 
     pub fn hash_footer(&mut self, input: &str, add_newline: bool) -> &mut Self {
@@ -259,10 +268,10 @@ impl TestTokenBuilder {
             self.newline();
         }
         self.footers.as_mut().unwrap().push(ExpectedValue {
-            full: format!("{}", value),
-            no_delims: format!("{}", value),
+            full: value.clone(),
+            no_delims: value,
         });
-        return self;
+        self
     }
 
     pub fn multi_line_footer(
@@ -282,15 +291,15 @@ impl TestTokenBuilder {
             no_delims: footer.clone(),
         });
         self.string = footer_builder.string;
-        return self;
+        self
     }
 
     fn get_token_buf(&self) -> Vec<TokenType> {
         self.test_token_buf.clone()
     }
 
-    pub fn generate_vec(&mut self) -> (Vec<Token>, TestStrings) {
-        let mut tokens: Vec<Token> = Vec::new();
+    pub fn generate_vec(&mut self) -> (Vec<Token<'static>>, TestStrings) {
+        let mut tokens: Vec<Token<'static>> = Vec::new();
         let slicable_rc_string = SlicableRcString::new(Rc::new(self.string.clone()));
         for token in self.test_token_buf.iter() {
             match token {
@@ -322,7 +331,7 @@ impl TestTokenBuilder {
                 )),
             }
         }
-        return (
+        (
             tokens,
             TestStrings {
                 topic: self.topic.take(),
@@ -331,12 +340,13 @@ impl TestTokenBuilder {
                 body: self.body.take(),
                 footers: self.footers.take(),
             },
-        );
+        )
     }
 
-    pub fn generate_iter(&mut self) -> (TokenIter, TestStrings) {
+    pub fn generate_iter(&mut self) -> (TokenIter<'static>, TestStrings) {
         let (tokens, expected) = self.generate_vec();
-        return (tokens.into_iter().multipeek(), expected);
+        let tokens: Box<dyn Iterator<Item = Token<'static>>> = Box::new(tokens.into_iter());
+        (tokens.multipeek(), expected)
     }
 }
 
@@ -379,7 +389,7 @@ mod tests {
         assert!(
             matches!(
                 expected.body.clone(),
-                Some(body) if body.no_delims == "this is a body\n"
+                Some(body) if body.no_delims == "this is a body"
             ),
             "Body should match (no delims)"
         );
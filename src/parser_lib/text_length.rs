@@ -0,0 +1,83 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// What lengths and columns are counted in
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LengthUnit {
+    Bytes,
+    /// Unicode scalar values, `é` written as `e` and a combining accent counts twice
+    Chars,
+    /// What readers see as one character, e.g. `👍🏽` or `é` in any form counts once
+    #[default]
+    Graphemes,
+}
+
+impl LengthUnit {
+    pub fn measure(&self, text: &str) -> usize {
+        match self {
+            LengthUnit::Bytes => text.len(),
+            LengthUnit::Chars => text.chars().count(),
+            LengthUnit::Graphemes => text.graphemes(true).count(),
+        }
+    }
+
+    /// 1-based line and column of a byte offset into `text`
+    pub fn position(&self, text: &str, offset: usize) -> Position {
+        let before = &text[..offset.min(text.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Position {
+            line: before.matches('\n').count() + 1,
+            column: self.measure(&before[line_start..]) + 1,
+        }
+    }
+}
+
+impl Display for LengthUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            LengthUnit::Bytes => "bytes",
+            LengthUnit::Chars => "code points",
+            LengthUnit::Graphemes => "characters",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_measure_in_each_unit() {
+        let text = "fix: café 👍🏽";
+        assert_eq!(LengthUnit::Bytes.measure(text), 19);
+        assert_eq!(LengthUnit::Chars.measure(text), 12);
+        assert_eq!(LengthUnit::Graphemes.measure(text), 11);
+    }
+
+    #[test]
+    fn should_find_position_of_offset() {
+        let text = "feat: ünïcode\n\nbödy x";
+        let offset = text.find('x').unwrap();
+        assert_eq!(
+            LengthUnit::Graphemes.position(text, offset),
+            Position { line: 3, column: 6 }
+        );
+        assert_eq!(LengthUnit::Bytes.position(text, offset).column, 7);
+    }
+}
@@ -0,0 +1,70 @@
+use std::ops::Range;
+
+/// A replacement for a span of the original commit message
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fix {
+    pub span: Range<usize>,
+    pub replacement: String,
+}
+
+impl Fix {
+    pub fn new(span: Range<usize>, replacement: &str) -> Self {
+        Self {
+            span,
+            replacement: replacement.to_string(),
+        }
+    }
+}
+
+/// Applies the fixes to the message. Fixes overlapping an already applied fix are skipped.
+///
+/// ```
+/// use parser_lib::{apply_fixes, CommitMessageParser};
+///
+/// let message = "fature: add users";
+/// let (_, errors) = CommitMessageParser::new()
+///     .process(message.to_string())
+///     .unwrap()
+///     .checked()
+///     .unwrap();
+/// let fixes: Vec<_> = errors
+///     .iter()
+///     .filter_map(|error| error.fix())
+///     .collect();
+/// assert_eq!(apply_fixes(message, &fixes), "feat: add users");
+/// ```
+pub fn apply_fixes(message: &str, fixes: &[Fix]) -> String {
+    let mut sorted: Vec<&Fix> = fixes.iter().collect();
+    sorted.sort_by_key(|fix| std::cmp::Reverse(fix.span.start));
+
+    let mut fixed = message.to_string();
+    let mut applied_from = message.len();
+    for fix in sorted {
+        if fix.span.end > applied_from {
+            continue;
+        }
+        fixed.replace_range(fix.span.clone(), &fix.replacement);
+        applied_from = fix.span.start;
+    }
+    fixed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_apply_fixes_back_to_front() {
+        let fixes = vec![Fix::new(0..6, "feat"), Fix::new(7..13, "parsing")];
+        assert_eq!(
+            apply_fixes("fature(paring): add x", &fixes),
+            "feat(parsing): add x"
+        );
+    }
+
+    #[test]
+    fn should_skip_overlapping_fixes() {
+        let fixes = vec![Fix::new(0..4, "fix"), Fix::new(2..6, "oops")];
+        assert_eq!(apply_fixes("feat: x", &fixes), "feoopsx");
+    }
+}
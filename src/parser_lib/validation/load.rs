@@ -2,30 +2,49 @@ use std::{
     env,
     fs::{metadata, read_to_string, File},
     io::{self, Result as ioResult, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
-use super::rules::ValidationRules;
+use toml::Table;
+
+use crate::parser_lib::git::is_git_root;
+
+use super::rules::{merge_tables, Preset, ValidationRules};
 
 const CONFIG_FILENAME: &str = "rclint.toml";
 
-fn try_parse_config(content: String) -> Result<ValidationRules, String> {
-    match toml::from_str(&content) {
-        Ok(rules) => Ok(rules),
-        Err(err) => Err(err.to_string()),
-    }
+pub(crate) fn try_parse_config(content: String) -> Result<ValidationRules, String> {
+    let config: Table = toml::from_str(&content).map_err(|err| err.to_string())?;
+    let preset: Preset = match config.get("preset") {
+        Some(preset) => preset.clone().try_into().map_err(|err| err.to_string())?,
+        None => Preset::default(),
+    };
+
+    let mut rules =
+        Table::try_from(ValidationRules::from_preset(preset)).map_err(|err| err.to_string())?;
+    merge_tables(&mut rules, config);
+
+    // Broken overrides fail here instead of on the first matching commit
+    rules.try_into().map_err(|err| err.to_string())
 }
 
 pub fn load_rules() -> ValidationRules {
     let path = look_for_conf_in(None);
     match path {
         Ok(Some(conf)) => {
-            let content = read_to_string(conf).unwrap();
+            let content = read_to_string(&conf).unwrap();
             match try_parse_config(content) {
-                Ok(rules) => rules,
+                Ok(mut rules) => {
+                    let root = conf.parent().unwrap_or(Path::new("."));
+                    if let Err(err) = rules.discover(root) {
+                        eprintln!("Error while reading allowed values: {}", err);
+                        eprintln!("Only the scopes listed in the config are allowed.");
+                    }
+                    rules
+                }
                 Err(err) => {
                     eprintln!("Error while parsing config file: {}", err);
-                    println!("Using default rules.");
+                    eprintln!("Using default rules.");
                     ValidationRules::default()
                 }
             }
@@ -49,11 +68,9 @@ fn look_for_conf_in(path: Option<PathBuf>) -> ioResult<Option<PathBuf>> {
         }
     }
 
-    if let Ok(meta) = metadata(&cur_dir.join(".git")) {
-        if meta.is_dir() {
-            // We are in a git project root, good enough
-            return create_default_config(&cur_dir);
-        }
+    if is_git_root(&cur_dir) {
+        // We are in a git project root, good enough
+        return create_default_config(&cur_dir);
     }
 
     match cur_dir.parent() {
@@ -66,9 +83,9 @@ fn look_for_conf_in(path: Option<PathBuf>) -> ioResult<Option<PathBuf>> {
     }
 }
 
-fn create_default_config(path: &PathBuf) -> ioResult<Option<PathBuf>> {
+fn create_default_config(path: &Path) -> ioResult<Option<PathBuf>> {
     let conf = path.join(CONFIG_FILENAME);
-    println!(
+    eprintln!(
         "No config file found. Creating default config into {:?}",
         conf
     );
@@ -77,8 +94,8 @@ fn create_default_config(path: &PathBuf) -> ioResult<Option<PathBuf>> {
     let toml = toml::to_string(&default_rules).unwrap();
     let mut file = File::create(&conf)?;
     file.write_all(toml.as_bytes())?;
-    println!("{}", toml);
-    return Ok(Some(conf));
+    eprintln!("{}", toml);
+    Ok(Some(conf))
 }
 
 #[cfg(test)]
@@ -104,12 +121,12 @@ mod tests {
         create_test_dir(".git", &dir);
 
         let ls_res = Command::new("ls")
-            .current_dir(&dir.path().join("src"))
+            .current_dir(dir.path().join("src"))
             .output()?;
 
         let lsa_res = Command::new("ls")
             .arg("-a")
-            .current_dir(&dir.path())
+            .current_dir(dir.path())
             .output()?;
 
         assert_eq!(String::from_utf8(ls_res.stdout).unwrap(), "bin\nlib\n");
@@ -135,4 +152,52 @@ mod tests {
             &mock_dir.join(CONFIG_FILENAME).to_str().unwrap()
         );
     }
+
+    #[test]
+    fn should_use_default_preset_when_none_selected() {
+        let rules = try_parse_config("".to_string()).unwrap();
+        assert_eq!(rules.preset(), Preset::Conventional);
+        assert!(rules.topic().allowed().contains("revert"));
+        assert!(rules.scope().allowed().is_empty());
+    }
+
+    #[test]
+    fn should_load_selected_preset() {
+        let rules = try_parse_config("preset = \"angular\"".to_string()).unwrap();
+        assert_eq!(rules.preset(), Preset::Angular);
+        assert!(!rules.topic().allowed().contains("chore"));
+        assert!(rules.topic().allowed().contains("revert"));
+        assert_eq!(rules.description().max_length(), 100);
+    }
+
+    #[test]
+    fn should_override_preset_values_from_config() {
+        let content = "preset = \"gitmoji\"\n[topic]\nallowed = [\"feat\", \"fix\"]\n";
+        let rules = try_parse_config(content.to_string()).unwrap();
+        assert_eq!(rules.preset(), Preset::Gitmoji);
+        assert_eq!(rules.topic().allowed().names(), ["feat", "fix"]);
+        assert_eq!(rules.description().max_length(), 120);
+    }
+
+    #[test]
+    fn should_keep_described_entries_in_config_order() {
+        let content = "[scope.allowed]\nparser = \"Parsing\"\ncli = \"Command line\"\nbench = \"Benchmarks\"\n";
+        let rules = try_parse_config(content.to_string()).unwrap();
+        assert_eq!(rules.scope().allowed().names(), ["parser", "cli", "bench"]);
+    }
+
+    #[test]
+    fn should_load_conditional_rules() {
+        let content = "[when.topic.feat]\nbody.required = true\n";
+        assert!(try_parse_config(content.to_string()).is_ok());
+
+        let content = "[when.topic.feat]\nbody.required = \"yes\"\n";
+        let err = try_parse_config(content.to_string()).unwrap_err();
+        assert!(err.starts_with("Invalid rules for topic 'feat'"));
+    }
+
+    #[test]
+    fn should_fail_on_unknown_preset() {
+        assert!(try_parse_config("preset = \"nope\"".to_string()).is_err());
+    }
 }
@@ -1,6 +1,10 @@
+mod fix;
 mod load;
 pub mod rules;
 mod validator;
 
+pub use self::fix::{apply_fixes, Fix};
 pub use self::load::load_rules;
+#[cfg(test)]
+pub(crate) use self::load::try_parse_config;
 pub use self::validator::Validator;
@@ -0,0 +1,292 @@
+use std::{cmp::Reverse, fmt::Display};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A single allowed topic or scope. Everything but the name is optional and
+/// only used to explain the value to the user, e.g. in error messages.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AllowedEntry {
+    #[serde(skip)]
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji: Option<String>,
+}
+
+impl AllowedEntry {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn described(name: &str, description: &str, title: &str, emoji: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            title: Some(title.to_string()),
+            emoji: Some(emoji.to_string()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Title to use e.g. for changelog sections, falls back to the name
+    pub fn title(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn emoji(&self) -> Option<&str> {
+        self.emoji.as_deref()
+    }
+
+    fn is_bare(&self) -> bool {
+        self.description.is_none() && self.title.is_none() && self.emoji.is_none()
+    }
+}
+
+impl Display for AllowedEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.description {
+            Some(description) => write!(f, "'{}' — {}", self.name, description),
+            None => write!(f, "'{}'", self.name),
+        }
+    }
+}
+
+/// Allowed values can be configured either as a plain list
+/// `allowed = ["feat", "fix"]` or as a table with descriptions
+/// `feat = "A new feature"` / `feat = { description = "...", title = "Features", emoji = "✨" }`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AllowedList(Vec<AllowedEntry>);
+
+impl AllowedList {
+    pub fn new(entries: Vec<AllowedEntry>) -> Self {
+        Self(entries)
+    }
+
+    /// Adds the entry unless one with the same name is already allowed
+    pub fn add(&mut self, entry: AllowedEntry) {
+        if !self.contains(&entry.name) {
+            self.0.push(entry);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AllowedEntry> {
+        self.0.iter().find(|entry| entry.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &AllowedEntry> {
+        self.0.iter()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.0.iter().map(|entry| entry.name()).collect()
+    }
+
+    /// Finds the entry closest to `value` by edit distance, e.g. `feat` for `fature`.
+    /// Entries that are too far off to be a plausible typo are not suggested.
+    pub fn closest(&self, value: &str) -> Option<&AllowedEntry> {
+        self.0
+            .iter()
+            .map(|entry| (edit_distance(value, &entry.name), entry))
+            .filter(|(distance, entry)| {
+                let longest = value.chars().count().max(entry.name.chars().count());
+                *distance * 3 <= longest * 2
+            })
+            // Typos rarely hit the first characters, prefer entries sharing a prefix on ties
+            .min_by_key(|(distance, entry)| (*distance, Reverse(common_prefix(value, &entry.name))))
+            .map(|(_, entry)| entry)
+    }
+}
+
+fn common_prefix(a: &str, b: &str) -> usize {
+    a.chars().zip(b.chars()).take_while(|(a, b)| a == b).count()
+}
+
+// Levenshtein distance counted in chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+impl Display for AllowedList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in self.0.iter() {
+            writeln!(f, "  {}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawEntry {
+    Description(String),
+    Detailed(AllowedEntry),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawAllowedList {
+    Names(Vec<String>),
+    // Keeps the configured order, e.g. for changelog sections and prompts
+    Described(IndexMap<String, RawEntry>),
+}
+
+impl Serialize for AllowedList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.iter().all(|entry| entry.is_bare()) {
+            return RawAllowedList::Names(self.0.iter().map(|e| e.name.clone()).collect())
+                .serialize(serializer);
+        }
+
+        // TOML writes plain values before sub-tables, so the forms can't be mixed without
+        // losing the configured order
+        let detailed = self
+            .0
+            .iter()
+            .any(|entry| entry.title.is_some() || entry.emoji.is_some());
+        let entries = self
+            .0
+            .iter()
+            .map(|entry| match &entry.description {
+                Some(description) if !detailed => (
+                    entry.name.clone(),
+                    RawEntry::Description(description.clone()),
+                ),
+                _ => (entry.name.clone(), RawEntry::Detailed(entry.clone())),
+            })
+            .collect();
+        RawAllowedList::Described(entries).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AllowedList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = match RawAllowedList::deserialize(deserializer)? {
+            RawAllowedList::Names(names) => names.iter().map(|n| AllowedEntry::new(n)).collect(),
+            RawAllowedList::Described(entries) => entries
+                .into_iter()
+                .map(|(name, entry)| match entry {
+                    RawEntry::Description(description) => AllowedEntry {
+                        name,
+                        description: Some(description),
+                        ..Default::default()
+                    },
+                    RawEntry::Detailed(entry) => AllowedEntry { name, ..entry },
+                })
+                .collect(),
+        };
+        Ok(Self(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Wrapper {
+        allowed: AllowedList,
+    }
+
+    #[test]
+    fn should_parse_plain_list() {
+        let wrapper: Wrapper = toml::from_str("allowed = [\"feat\", \"fix\"]").unwrap();
+        assert_eq!(wrapper.allowed.names(), ["feat", "fix"]);
+        assert!(wrapper.allowed.get("feat").unwrap().description().is_none());
+    }
+
+    #[test]
+    fn should_parse_described_entries() {
+        let content = "[allowed]\nfeat = \"A new feature\"\nfix = { description = \"A bug fix\", title = \"Bug Fixes\", emoji = \"🐛\" }\n";
+        let wrapper: Wrapper = toml::from_str(content).unwrap();
+        let feat = wrapper.allowed.get("feat").unwrap();
+        assert_eq!(feat.description(), Some("A new feature"));
+        assert_eq!(feat.title(), "feat");
+        let fix = wrapper.allowed.get("fix").unwrap();
+        assert_eq!(fix.title(), "Bug Fixes");
+        assert_eq!(fix.emoji(), Some("🐛"));
+    }
+
+    #[test]
+    fn should_keep_configured_order() {
+        let content =
+            "[allowed]\nfix = \"A bug fix\"\nfeat = \"A new feature\"\nchore = \"Maintenance\"\n";
+        let wrapper: Wrapper = toml::from_str(content).unwrap();
+        assert_eq!(wrapper.allowed.names(), ["fix", "feat", "chore"]);
+
+        let content = toml::to_string(&wrapper).unwrap();
+        let parsed: Wrapper = toml::from_str(&content).unwrap();
+        assert_eq!(parsed.allowed.names(), ["fix", "feat", "chore"]);
+    }
+
+    #[test]
+    fn should_calculate_edit_distance() {
+        assert_eq!(edit_distance("feat", "feat"), 0);
+        assert_eq!(edit_distance("paring", "parsing"), 1);
+        assert_eq!(edit_distance("feature", "feat"), 3);
+        assert_eq!(edit_distance("", "fix"), 3);
+    }
+
+    #[test]
+    fn should_suggest_closest_entry() {
+        let allowed = AllowedList::new(
+            ["chore", "feat", "fix", "docs", "parsing", "lexing"]
+                .iter()
+                .map(|name| AllowedEntry::new(name))
+                .collect(),
+        );
+        assert_eq!(allowed.closest("fature").unwrap().name(), "feat");
+        assert_eq!(allowed.closest("feature").unwrap().name(), "feat");
+        assert_eq!(allowed.closest("paring").unwrap().name(), "parsing");
+        assert_eq!(allowed.closest("fxi").unwrap().name(), "fix");
+        assert!(allowed.closest("banana").is_none());
+    }
+
+    #[test]
+    fn should_round_trip_through_toml() {
+        let wrapper = Wrapper {
+            allowed: AllowedList::new(vec![
+                AllowedEntry::described("feat", "A new feature", "Features", "✨"),
+                AllowedEntry {
+                    name: "fix".to_string(),
+                    description: Some("A bug fix".to_string()),
+                    ..Default::default()
+                },
+            ]),
+        };
+        let content = toml::to_string(&wrapper).unwrap();
+        let parsed: Wrapper = toml::from_str(&content).unwrap();
+        assert_eq!(parsed.allowed, wrapper.allowed);
+    }
+}
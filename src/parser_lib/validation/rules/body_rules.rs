@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::parser_lib::{errors::ValidationError, parsing::types::CommitMessage};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BodyRules {
     max_length: u32,
@@ -16,3 +18,13 @@ impl Default for BodyRules {
         }
     }
 }
+
+impl BodyRules {
+    pub fn validate(&self, message: &CommitMessage) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        if self.required && message.body.is_none() {
+            errors.push(ValidationError::MissingBody);
+        }
+        errors
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use toml::{Table, Value};
+
+use super::pattern::Pattern;
+
+const MANIFEST: &str = "Cargo.toml";
+
+fn read_manifest(dir: &Path) -> Result<Table, String> {
+    let path = dir.join(MANIFEST);
+    let content = fs::read_to_string(&path).map_err(|err| format!("{:?}: {}", path, err))?;
+    toml::from_str(&content).map_err(|err| format!("{:?}: {}", path, err))
+}
+
+fn strings<'a>(table: Option<&'a Table>, key: &str) -> Vec<&'a str> {
+    table
+        .and_then(|table| table.get(key))
+        .and_then(Value::as_array)
+        .map(|values| values.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn package_name(manifest: &Table) -> Option<String> {
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+// Directories matching a member like `crates/*`, as paths relative to the root
+fn expand(root: &Path, member: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![PathBuf::new()];
+    for segment in member.split('/').filter(|segment| !segment.is_empty()) {
+        if !segment.contains(['*', '?']) {
+            dirs.iter_mut().for_each(|dir| dir.push(segment));
+            continue;
+        }
+        let glob = Pattern::from_glob(segment);
+        dirs = dirs
+            .iter()
+            .filter_map(|dir| fs::read_dir(root.join(dir)).ok())
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter(|entry| glob.is_match(&entry.file_name().to_string_lossy()))
+            .map(|entry| entry.path().strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+    }
+    dirs
+}
+
+/// Package names of the workspace whose manifest is in `root`, including the root package
+pub fn workspace_packages(root: &Path) -> Result<Vec<String>, String> {
+    let manifest = read_manifest(root)?;
+    let workspace = manifest.get("workspace").and_then(Value::as_table);
+    let excluded: Vec<&Path> = strings(workspace, "exclude")
+        .into_iter()
+        .map(Path::new)
+        .collect();
+
+    let mut names: Vec<String> = package_name(&manifest).into_iter().collect();
+    for member in strings(workspace, "members") {
+        for dir in expand(root, member) {
+            if excluded.contains(&dir.as_path()) {
+                continue;
+            }
+            let member_manifest = read_manifest(&root.join(&dir))?;
+            let name = package_name(&member_manifest)
+                .ok_or_else(|| format!("{:?} has no package name", dir.join(MANIFEST)))?;
+            names.push(name);
+        }
+    }
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn write_manifest(root: &Path, dir: &str, content: &str) {
+        fs::create_dir_all(root.join(dir)).unwrap();
+        fs::write(root.join(dir).join(MANIFEST), content).unwrap();
+    }
+
+    #[test]
+    fn should_list_workspace_packages() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write_manifest(
+            root,
+            "",
+            "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\nexclude = [\"crates/old\"]\n",
+        );
+        write_manifest(root, "crates/api", "[package]\nname = \"my-api\"\n");
+        write_manifest(root, "crates/core", "[package]\nname = \"core\"\n");
+        write_manifest(root, "crates/old", "[package]\nname = \"old\"\n");
+        write_manifest(root, "tools/cli", "[package]\nname = \"cli\"\n");
+
+        assert_eq!(workspace_packages(root).unwrap(), ["cli", "core", "my-api"]);
+    }
+
+    #[test]
+    fn should_fail_on_member_without_manifest() {
+        let dir = tempdir().unwrap();
+        write_manifest(dir.path(), "", "[workspace]\nmembers = [\"api\"]\n");
+        assert!(workspace_packages(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,26 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use toml::Table;
+
+/// Rule overrides that only apply to some commits, e.g. `body.required = true`
+/// under `[when.topic.feat]`. The overrides use the same layout as the config file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Conditions {
+    #[serde(default)]
+    topic: BTreeMap<String, Table>,
+}
+
+impl Conditions {
+    pub fn is_empty(&self) -> bool {
+        self.topic.is_empty()
+    }
+
+    pub fn topics(&self) -> impl Iterator<Item = &str> {
+        self.topic.keys().map(|topic| topic.as_str())
+    }
+
+    pub fn for_topic(&self, topic: &str) -> Option<&Table> {
+        self.topic.get(topic)
+    }
+}
@@ -1,5 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use crate::parser_lib::{
+    errors::ValidationError, parsing::types::CommitMessage, text_length::LengthUnit,
+};
+
+use super::presets::Preset;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DescriptionRules {
     max_length: u32,
@@ -7,12 +13,71 @@ pub struct DescriptionRules {
     required: bool,
 }
 
-impl Default for DescriptionRules {
-    fn default() -> Self {
+impl DescriptionRules {
+    pub fn for_preset(preset: Preset) -> Self {
+        let max_length = match preset {
+            Preset::Conventional | Preset::Angular => 100,
+            Preset::Gitmoji => 120,
+        };
+
         Self {
-            max_length: 120,
+            max_length,
             space_afer_colon: true,
             required: true,
         }
     }
+
+    pub fn max_length(&self) -> u32 {
+        self.max_length
+    }
+
+    pub fn validate(&self, message: &CommitMessage, unit: LengthUnit) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        if let Some(description) = &message.description {
+            let length = description.content_length_in(unit);
+            let max = self.max_length as usize;
+            if length > max {
+                errors.push(ValidationError::DescriptionTooLong {
+                    length,
+                    max,
+                    unit,
+                    span: description.content_span(),
+                });
+            }
+        }
+        errors
+    }
+}
+
+impl Default for DescriptionRules {
+    fn default() -> Self {
+        Self::for_preset(Preset::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser_lib::test_utils::parse_message;
+
+    #[test]
+    fn should_measure_description_in_configured_unit() {
+        let rules: DescriptionRules =
+            toml::from_str("max_length = 10\nspace_afer_colon = true\nrequired = true").unwrap();
+        // 9 characters, 11 code points and 18 bytes
+        let message = parse_message("feat: cafe\u{301} 👍🏽 ok");
+        assert!(rules.validate(&message, LengthUnit::Graphemes).is_empty());
+        assert!(matches!(
+            &rules.validate(&message, LengthUnit::Chars)[0],
+            ValidationError::DescriptionTooLong {
+                length: 11,
+                max: 10,
+                ..
+            }
+        ));
+        assert_eq!(
+            rules.validate(&message, LengthUnit::Bytes)[0].to_string(),
+            "Validation error: Description is 18 bytes long, the maximum is 10."
+        );
+    }
 }
@@ -1,14 +1,356 @@
+use std::{collections::BTreeMap, ops::Range};
+
 use serde::{Deserialize, Serialize};
 
+use crate::parser_lib::{
+    errors::ValidationError,
+    git::{parse_trailers, DEFAULT_SEPARATORS},
+    parsing::{
+        determine_text_case,
+        types::{CommitMessage, TextCase},
+    },
+};
+
+use super::pattern::Pattern;
+
+// The spec allows both spellings and requires them to be uppercase
+const BREAKING_CHANGE_KEYS: [&str; 2] = ["BREAKING CHANGE", "BREAKING-CHANGE"];
+
+/// How footers are found in a message
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FooterMode {
+    /// `Key: value` and `Key #value` lines as parsed by the commit message parser
+    #[default]
+    Conventional,
+    /// The trailer block as `git interpret-trailers` finds it
+    GitTrailers,
+}
+
+fn default_separators() -> String {
+    DEFAULT_SEPARATORS.to_string()
+}
+
+// A footer or a git trailer, depending on the mode
+struct Footer {
+    key: String,
+    key_span: Range<usize>,
+    value: String,
+    span: Range<usize>,
+    multi_line: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FooterKeyRules {
+    /// Regex the value has to match, e.g. `^#\d+$` for `Refs: #123` or `Refs #123`
+    pattern: Option<Pattern>,
+    /// Topics for which the footer has to be present
+    #[serde(default)]
+    required_for: Vec<String>,
+    max_occurrences: Option<usize>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FooterRules {
     allow_multi_line: bool,
+    /// An empty list allows any key
+    #[serde(default)]
+    allowed_keys: Vec<String>,
+    /// Keys that have to be present on every commit
+    #[serde(default)]
+    required: Vec<String>,
+    key_case: Option<TextCase>,
+    /// Rules for specific keys, keys are matched case insensitively like git does
+    #[serde(default)]
+    keys: BTreeMap<String, FooterKeyRules>,
+    #[serde(default)]
+    mode: FooterMode,
+    /// Characters that separate key and value in `git-trailers` mode, like git's `trailer.separators`
+    #[serde(default = "default_separators")]
+    separators: String,
 }
 
 impl Default for FooterRules {
     fn default() -> Self {
         Self {
             allow_multi_line: true,
+            allowed_keys: Vec::new(),
+            required: Vec::new(),
+            key_case: None,
+            keys: BTreeMap::new(),
+            mode: FooterMode::default(),
+            separators: default_separators(),
+        }
+    }
+}
+
+impl FooterRules {
+    fn key_rules(&self, key: &str) -> Option<&FooterKeyRules> {
+        self.keys
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, rules)| rules)
+    }
+
+    fn required_keys(&self, topic: Option<&str>) -> Vec<&str> {
+        let mut required: Vec<&str> = self.required.iter().map(|key| key.as_str()).collect();
+        for (key, rules) in self.keys.iter() {
+            if let Some(topic) = topic {
+                if rules.required_for.iter().any(|t| t == topic) {
+                    required.push(key);
+                }
+            }
+        }
+        required
+    }
+
+    fn footers(&self, message: &CommitMessage) -> Vec<Footer> {
+        match self.mode {
+            FooterMode::Conventional => message
+                .footers
+                .iter()
+                .flatten()
+                .filter_map(|footer| {
+                    let value = footer.footer_value()?;
+                    Some(Footer {
+                        key: footer.footer_key()?.to_string(),
+                        key_span: footer.footer_key_span()?,
+                        multi_line: value.contains('\n'),
+                        value,
+                        span: footer.content_span(),
+                    })
+                })
+                .collect(),
+            FooterMode::GitTrailers => message
+                .source()
+                .map(|source| parse_trailers(source, &self.separators))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|trailer| Footer {
+                    key: trailer.key,
+                    key_span: trailer.key_span,
+                    // Git sees `#` in `Refs #12` as the separator, keep it like conventional footers do
+                    value: match trailer.separator {
+                        '#' => format!("#{}", trailer.value),
+                        _ => trailer.value,
+                    },
+                    span: trailer.span,
+                    multi_line: trailer.folded,
+                })
+                .collect(),
+        }
+    }
+
+    fn validate_footer(&self, footer: &Footer, errors: &mut Vec<ValidationError>) {
+        let key = &footer.key;
+        let span = footer.key_span.clone();
+        if BREAKING_CHANGE_KEYS.contains(&key.as_str()) {
+            return;
+        }
+
+        if !self.allowed_keys.is_empty()
+            && !self
+                .allowed_keys
+                .iter()
+                .any(|k| k.eq_ignore_ascii_case(key))
+        {
+            errors.push(ValidationError::FooterKeyNotAllowed {
+                key: key.clone(),
+                span: span.clone(),
+                allowed: self.allowed_keys.clone(),
+            });
+        }
+
+        if let Some(expected) = self.key_case {
+            if !determine_text_case(key).fits(expected) {
+                errors.push(ValidationError::FooterKeyCase {
+                    key: key.clone(),
+                    span: span.clone(),
+                    expected,
+                });
+            }
+        }
+
+        if !self.allow_multi_line && footer.multi_line {
+            errors.push(ValidationError::MultiLineFooter {
+                key: key.clone(),
+                span: footer.span.clone(),
+            });
+        }
+
+        if let Some(pattern) = self.key_rules(key).and_then(|rules| rules.pattern.as_ref()) {
+            if !pattern.is_match(&footer.value) {
+                errors.push(ValidationError::InvalidFooterValue {
+                    key: key.clone(),
+                    value: footer.value.clone(),
+                    span: footer.span.clone(),
+                    pattern: pattern.to_string(),
+                });
+            }
+        }
+    }
+
+    pub fn validate(&self, message: &CommitMessage) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let footers = self.footers(message);
+        let keys: Vec<&str> = footers.iter().map(|footer| footer.key.as_str()).collect();
+
+        for footer in footers.iter() {
+            self.validate_footer(footer, &mut errors);
+        }
+
+        for (name, rules) in self.keys.iter() {
+            let count = keys.iter().filter(|k| k.eq_ignore_ascii_case(name)).count();
+            match rules.max_occurrences {
+                Some(max) if count > max => errors.push(ValidationError::TooManyFooters {
+                    key: name.clone(),
+                    count,
+                    max,
+                }),
+                _ => (),
+            }
+        }
+
+        let topic = message.topic.as_ref().map(|topic| topic.content_string());
+        for required in self.required_keys(topic.as_deref()) {
+            if !keys.iter().any(|k| k.eq_ignore_ascii_case(required)) {
+                errors.push(ValidationError::MissingFooter {
+                    key: required.to_string(),
+                    topic: topic.clone(),
+                });
+            }
         }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser_lib::test_utils::parse_message;
+
+    fn rules(config: &str) -> FooterRules {
+        toml::from_str(&format!("allow_multi_line = true\n{}", config)).unwrap()
+    }
+
+    #[test]
+    fn should_require_footer_for_topic() {
+        let rules = rules("[keys.Refs]\nrequired_for = [\"fix\"]\n");
+        let errors = rules.validate(&parse_message("fix: handle empty input"));
+        assert_eq!(
+            errors,
+            vec![ValidationError::MissingFooter {
+                key: "Refs".to_string(),
+                topic: Some("fix".to_string())
+            }]
+        );
+        assert!(rules.validate(&parse_message("feat: add users")).is_empty());
+        assert!(rules
+            .validate(&parse_message("fix: handle empty input\n\nrefs: #12"))
+            .is_empty());
+    }
+
+    #[test]
+    fn should_check_value_pattern() {
+        let rules = rules(
+            "[keys.Refs]\npattern = '^#\\d+$'\n\
+             [keys.Signed-off-by]\npattern = '^.+ <[^>]+@[^>]+>$'\n",
+        );
+        let valid = "fix: x\n\nRefs #12\nSigned-off-by: Jane Doe <jane@example.com>";
+        assert!(rules.validate(&parse_message(valid)).is_empty());
+
+        let errors = rules.validate(&parse_message("fix: x\n\nRefs: 12\nSigned-off-by: Jane"));
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            &errors[0],
+            ValidationError::InvalidFooterValue { key, value, .. } if key == "Refs" && value == "12"
+        ));
+    }
+
+    #[test]
+    fn should_check_value_pattern_the_same_in_both_modes() {
+        for mode in ["conventional", "git-trailers"] {
+            let rules = rules(&format!(
+                "mode = \"{}\"\nseparators = \":#\"\n[keys.Refs]\npattern = '^#\\d+$'\n",
+                mode
+            ));
+            for valid in ["fix: x\n\nRefs #12", "fix: x\n\nRefs: #12"] {
+                assert!(rules.validate(&parse_message(valid)).is_empty(), "{}", mode);
+            }
+            let errors = rules.validate(&parse_message("fix: x\n\nRefs: 12"));
+            assert!(
+                matches!(&errors[..], [ValidationError::InvalidFooterValue { value, .. }] if value == "12"),
+                "{}",
+                mode
+            );
+        }
+    }
+
+    #[test]
+    fn should_check_allowed_keys_and_occurrences() {
+        let rules =
+            rules("allowed_keys = [\"Refs\", \"Reviewed-by\"]\n[keys.Refs]\nmax_occurrences = 1\n");
+        let errors = rules.validate(&parse_message(
+            "feat: x\n\nRefs: #1\nRefs: #2\nAcked-by: someone\nBREAKING-CHANGE: gone",
+        ));
+        assert_eq!(errors.len(), 2);
+        assert!(
+            matches!(&errors[0], ValidationError::FooterKeyNotAllowed { key, .. } if key == "Acked-by")
+        );
+        assert!(matches!(
+            &errors[1],
+            ValidationError::TooManyFooters {
+                count: 2,
+                max: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn should_check_key_case() {
+        let rules = rules("key_case = \"capitalized-kebab\"\n");
+        assert!(rules
+            .validate(&parse_message("feat: x\n\nRefs: #1\nSigned-off-by: Jane"))
+            .is_empty());
+        let errors = rules.validate(&parse_message("feat: x\n\nsigned-off-by: Jane"));
+        assert!(matches!(
+            &errors[0],
+            ValidationError::FooterKeyCase {
+                expected: TextCase::CapitalizedKebab,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn should_read_git_trailers_in_trailer_mode() {
+        let rules = rules(
+            "allowed_keys = [\"Co-authored-by\", \"Refs\"]\nmode = \"git-trailers\"\n\
+             separators = \":#\"\n[keys.Refs]\npattern = '^#\\d+$'\n",
+        );
+        let message = "feat: x\n\nbody\n\nRefs #12\nCo-authored-by: Jane Doe\n  <jane@example.com>";
+        assert!(rules.validate(&parse_message(message)).is_empty());
+
+        // Mostly prose, so git doesn't see trailers there
+        let errors = rules.validate(&parse_message(
+            "feat: x\n\nsome prose\nAcked-by: Joe\nmore prose",
+        ));
+        assert!(errors.is_empty());
+        let errors = rules.validate(&parse_message("feat: x\n\nAcked-by: Joe"));
+        assert!(
+            matches!(&errors[0], ValidationError::FooterKeyNotAllowed { key, .. } if key == "Acked-by")
+        );
+    }
+
+    #[test]
+    fn should_reject_multi_line_footers_when_not_allowed() {
+        let rules: FooterRules = toml::from_str("allow_multi_line = false").unwrap();
+        let errors = rules.validate(&parse_message("feat: x\n\nNote: first line\nsecond line"));
+        assert!(matches!(
+            &errors[0],
+            ValidationError::MultiLineFooter { .. }
+        ));
     }
 }
@@ -0,0 +1,130 @@
+use std::{ops::Range, sync::OnceLock};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::pattern::Pattern;
+
+// Messages written by git or bots rather than by people
+const DEFAULT_PATTERNS: [&str; 4] = [
+    r"^Merge (branch|branches|remote-tracking branch|tag|commit|pull request|request) ",
+    r"^Merged in ",
+    r"^Bump \S+ from \S+ to \S+",
+    r"(?m)^Signed-off-by: dependabot\[bot\]",
+];
+
+fn default_regexes() -> &'static [Regex] {
+    static REGEXES: OnceLock<Vec<Regex>> = OnceLock::new();
+    REGEXES.get_or_init(|| {
+        DEFAULT_PATTERNS
+            .iter()
+            .map(|pattern| Regex::new(pattern).unwrap())
+            .collect()
+    })
+}
+
+// `git commit --fixup` prefixes the header of the target commit, possibly more than once
+fn fixup_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^((fixup|squash|amend)! )+").unwrap())
+}
+
+// Missing fields take their value from `Default`, so `defaults` stays on unless turned off
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IgnoreRules {
+    /// Skip merge, `fixup!`, `squash!`, `amend!` and dependabot commits
+    defaults: bool,
+    /// Regexes matched against the whole message, matching messages are skipped
+    patterns: Vec<Pattern>,
+    /// Lint the header of the target of `fixup!`, `squash!` and `amend!` commits instead of skipping them
+    validate_fixup_target: bool,
+}
+
+impl Default for IgnoreRules {
+    fn default() -> Self {
+        Self {
+            defaults: true,
+            patterns: Vec::new(),
+            validate_fixup_target: false,
+        }
+    }
+}
+
+impl IgnoreRules {
+    /// The part of the message that should be linted, `None` when the message is ignored
+    pub fn lint_span(&self, message: &str) -> Option<Range<usize>> {
+        if self
+            .patterns
+            .iter()
+            .any(|pattern| pattern.is_match(message))
+        {
+            return None;
+        }
+        if !self.defaults {
+            return Some(0..message.len());
+        }
+
+        if let Some(prefix) = fixup_regex().find(message) {
+            if !self.validate_fixup_target {
+                return None;
+            }
+            let header_end = message.find('\n').unwrap_or(message.len());
+            return Some(prefix.end()..header_end);
+        }
+        if default_regexes()
+            .iter()
+            .any(|regex| regex.is_match(message))
+        {
+            return None;
+        }
+        Some(0..message.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_ignore_generated_messages() {
+        let rules = IgnoreRules::default();
+        for message in [
+            "Merge branch 'main' into feature",
+            "Merge pull request #12 from org/feature\n\nfeat: add users",
+            "fixup! feat: add users",
+            "squash! fixup! feat: add users",
+            "Bump serde from 1.0.1 to 1.0.2",
+            "build(deps): bump serde\n\nSigned-off-by: dependabot[bot] <support@github.com>",
+        ] {
+            assert_eq!(rules.lint_span(message), None, "{}", message);
+        }
+        assert_eq!(rules.lint_span("feat: add users"), Some(0..15));
+        assert_eq!(rules.lint_span("Merge the users"), Some(0..15));
+    }
+
+    #[test]
+    fn should_lint_fixup_target_when_enabled() {
+        let rules: IgnoreRules = toml::from_str("validate_fixup_target = true").unwrap();
+        let message = "fixup! amend! feat: add users\n\nmore";
+        assert_eq!(
+            &message[rules.lint_span(message).unwrap()],
+            "feat: add users"
+        );
+    }
+
+    #[test]
+    fn should_apply_configured_patterns() {
+        let rules: IgnoreRules = toml::from_str("defaults = false\npatterns = ['^WIP']").unwrap();
+        assert_eq!(rules.lint_span("WIP: users"), None);
+        assert_eq!(rules.lint_span("fixup! x"), Some(0..8));
+    }
+
+    #[test]
+    fn should_default_missing_fields() {
+        let rules: IgnoreRules = toml::from_str("patterns = ['^WIP']").unwrap();
+        assert!(rules.defaults);
+        assert!(!rules.validate_fixup_target);
+        assert_eq!(rules.lint_span("Merge branch 'main'"), None);
+    }
+}
@@ -1,33 +1,231 @@
+mod allowed;
 mod body_rules;
+mod cargo_workspace;
+mod conditions;
 mod description_rules;
 mod footer_rules;
+mod ignore_rules;
+mod pattern;
+mod presets;
+mod reference_rules;
 mod scope_rules;
 mod topic_rules;
 
-use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, ops::Range, path::Path};
 
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use toml::{Table, Value};
+
+use crate::parser_lib::{
+    errors::ValidationError,
+    parsing::types::{CommitMessage, ParseMode},
+    text_length::LengthUnit,
+};
+
+pub use self::allowed::{AllowedEntry, AllowedList};
+pub use self::description_rules::DescriptionRules;
+pub use self::presets::Preset;
+pub use self::scope_rules::ScopeRules;
+pub use self::topic_rules::TopicRules;
 use self::{
-    body_rules::BodyRules, description_rules::DescriptionRules, footer_rules::FooterRules,
-    scope_rules::ScopeRules, topic_rules::TopicRules,
+    body_rules::BodyRules, conditions::Conditions, footer_rules::FooterRules,
+    ignore_rules::IgnoreRules, reference_rules::ReferenceRules,
 };
 
+// Values from the overrides replace the values of the base table
+pub(crate) fn merge_tables(base: &mut Table, overrides: Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base_table)), Value::Table(override_table)) => {
+                merge_tables(base_table, override_table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+// Derived as inherent functions, the trait impls resolve the conditions after deserializing
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct ValidationRules {
+    preset: Preset,
+    #[serde(default)]
+    parse_mode: ParseMode,
+    /// Whitespace the lenient parse mode accepted fails validation instead of being a warning
+    #[serde(default)]
+    style_errors: bool,
+    /// Unit of length limits and error columns
+    #[serde(default)]
+    length_unit: LengthUnit,
     topic: TopicRules,
     scope: ScopeRules,
     description: DescriptionRules,
     body: BodyRules,
     footers: FooterRules,
+    #[serde(default)]
+    references: ReferenceRules,
+    #[serde(default)]
+    ignore: IgnoreRules,
+    #[serde(default, skip_serializing_if = "Conditions::is_empty")]
+    when: Conditions,
+    /// The rules with the overrides of `when` applied, by topic
+    #[serde(skip)]
+    topic_rules: BTreeMap<String, ValidationRules>,
 }
 
-impl Default for ValidationRules {
-    fn default() -> Self {
+impl Serialize for ValidationRules {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ValidationRules::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ValidationRules {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut rules = ValidationRules::deserialize(deserializer)?;
+        rules.resolve_conditions().map_err(D::Error::custom)?;
+        Ok(rules)
+    }
+}
+
+impl ValidationRules {
+    pub fn from_preset(preset: Preset) -> Self {
         Self {
-            topic: TopicRules::default(),
-            scope: ScopeRules::default(),
-            description: DescriptionRules::default(),
+            preset,
+            parse_mode: ParseMode::default(),
+            style_errors: false,
+            length_unit: LengthUnit::default(),
+            topic: TopicRules::for_preset(preset),
+            scope: ScopeRules::for_preset(preset),
+            description: DescriptionRules::for_preset(preset),
             body: BodyRules::default(),
             footers: FooterRules::default(),
+            references: ReferenceRules::default(),
+            ignore: IgnoreRules::default(),
+            when: Conditions::default(),
+            topic_rules: BTreeMap::new(),
+        }
+    }
+
+    pub fn preset(&self) -> Preset {
+        self.preset
+    }
+
+    pub fn parse_mode(&self) -> ParseMode {
+        self.parse_mode
+    }
+
+    pub fn length_unit(&self) -> LengthUnit {
+        self.length_unit
+    }
+
+    pub fn topic(&self) -> &TopicRules {
+        &self.topic
+    }
+
+    pub fn scope(&self) -> &ScopeRules {
+        &self.scope
+    }
+
+    pub fn description(&self) -> &DescriptionRules {
+        &self.description
+    }
+
+    /// The part of the raw message that should be linted, `None` when the message is ignored.
+    /// Merge, fixup and bot commits don't follow the format, so this runs before the parser.
+    pub fn lint_span(&self, message: &str) -> Option<Range<usize>> {
+        self.ignore.lint_span(message)
+    }
+
+    /// The rules with the overrides from `[when.topic.<topic>]` applied,
+    /// `None` when there are no overrides for the topic
+    pub fn for_topic(&self, topic: &str) -> Option<&ValidationRules> {
+        self.topic_rules.get(topic)
+    }
+
+    // Overrides are merged into the config once, so validating doesn't rebuild the rules
+    fn resolve_conditions(&mut self) -> Result<(), String> {
+        let mut topic_rules = BTreeMap::new();
+        for topic in self.when.topics() {
+            let overrides = self.when.for_topic(topic).cloned().unwrap_or_default();
+            let invalid = |err: String| format!("Invalid rules for topic '{}': {}", topic, err);
+            if overrides.contains_key("when") {
+                return Err(invalid("conditions can't be nested".to_string()));
+            }
+
+            let mut rules = Table::try_from(&*self).map_err(|err| invalid(err.to_string()))?;
+            rules.remove("when");
+            merge_tables(&mut rules, overrides);
+            let rules: ValidationRules = rules
+                .try_into()
+                .map_err(|err: toml::de::Error| invalid(err.to_string()))?;
+            topic_rules.insert(topic.to_string(), rules);
+        }
+        self.topic_rules = topic_rules;
+        Ok(())
+    }
+
+    /// Fills in values the config says to read from the project in `root`
+    pub fn discover(&mut self, root: &Path) -> Result<(), String> {
+        for rules in self.topic_rules.values_mut() {
+            rules.discover(root)?;
+        }
+        self.scope.discover(root)
+    }
+
+    /// Whitespace the lenient parse mode accepted, empty when `style_errors` makes it fail
+    /// validation instead
+    pub fn style_warnings(&self, message: &CommitMessage) -> Vec<ValidationError> {
+        if self.style_errors {
+            return Vec::new();
         }
+        message
+            .diagnostics
+            .iter()
+            .map(|diagnostic| ValidationError::StyleWarning {
+                issue: diagnostic.issue,
+                span: diagnostic.span.clone(),
+            })
+            .collect()
+    }
+
+    pub fn validate(&self, message: &CommitMessage) -> Vec<ValidationError> {
+        let mut errors: Vec<ValidationError> = message
+            .diagnostics
+            .iter()
+            .filter(|_| self.style_errors)
+            .map(|diagnostic| ValidationError::Style {
+                issue: diagnostic.issue,
+                span: diagnostic.span.clone(),
+            })
+            .collect();
+
+        let topic = message.topic.as_ref().map(|topic| topic.content_string());
+        let rules = topic
+            .and_then(|topic| self.for_topic(&topic))
+            .unwrap_or(self);
+        errors.extend(rules.validate_parts(message));
+        errors
+    }
+
+    fn validate_parts(&self, message: &CommitMessage) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        // Git writes its own revert headers, there's no topic, scope or description to pick
+        if !matches!(message.revert(), Some(revert) if revert.generated_by_git) {
+            errors.extend(self.topic.validate(message));
+            errors.extend(self.scope.validate(message));
+            errors.extend(self.description.validate(message, self.length_unit));
+        }
+        errors.extend(self.body.validate(message));
+        errors.extend(self.footers.validate(message));
+        errors.extend(self.references.validate(message));
+        errors
+    }
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        Self::from_preset(Preset::default())
     }
 }
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Named rule sets that a config file can select with `preset = "<name>"`.
+/// Everything else in the config file is applied on top of the selected preset.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Preset {
+    /// Mirrors commitlint's `config-conventional`
+    #[default]
+    Conventional,
+    /// Mirrors the Angular commit message guidelines
+    Angular,
+    /// Conventional types with more room in the description for emoji
+    Gitmoji,
+}
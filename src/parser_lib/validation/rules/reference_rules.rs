@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+use crate::parser_lib::{
+    errors::ValidationError,
+    parsing::types::{CommitMessage, IssueReference},
+};
+
+use super::pattern::Pattern;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReferenceRules {
+    /// Every commit has to reference an issue
+    required: bool,
+    /// Patterns of the accepted trackers, e.g. `^PROJ-\d+$` or `^https://tracker\.example/`.
+    /// An empty list accepts `#123` and `PROJ-456`, but not links since most of them aren't issues.
+    #[serde(default)]
+    patterns: Vec<Pattern>,
+}
+
+impl ReferenceRules {
+    pub fn validate(&self, message: &CommitMessage) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        if !self.required {
+            return errors;
+        }
+
+        let has_reference = message.references().iter().any(|reference| {
+            if self.patterns.is_empty() {
+                return !matches!(reference, IssueReference::Url { .. });
+            }
+            let text = reference.to_string();
+            self.patterns.iter().any(|p| p.is_match(&text))
+        });
+        if !has_reference {
+            errors.push(ValidationError::MissingReference {
+                patterns: self.patterns.iter().map(|p| p.to_string()).collect(),
+            });
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser_lib::test_utils::parse_message;
+
+    #[test]
+    fn should_extract_references() {
+        let message = "fix: handle empty input, see #12\n\n\
+                       Reported in https://github.com/org/repo/issues/7\n\n\
+                       Refs #34\nJira: PROJ-456";
+        let references = parse_message(message).references();
+        assert_eq!(
+            references
+                .iter()
+                .map(|reference| reference.to_string())
+                .collect::<Vec<_>>(),
+            [
+                "#12",
+                "https://github.com/org/repo/issues/7",
+                "#34",
+                "PROJ-456"
+            ]
+        );
+        assert_eq!(
+            references[0],
+            IssueReference::Number {
+                number: 12,
+                span: 29..32
+            }
+        );
+        assert_eq!(&message[references[3].span()], "PROJ-456");
+    }
+
+    #[test]
+    fn should_ignore_hashes_inside_words() {
+        assert!(parse_message("fix: escape C#1 and a#b")
+            .references()
+            .is_empty());
+    }
+
+    #[test]
+    fn should_ignore_standards_and_hashes() {
+        let message =
+            "fix: use SHA-256 and UTF-8\n\nDates are ISO-8601 now, see CVE-2024 and UTF8-3";
+        assert!(parse_message(message).references().is_empty());
+    }
+
+    #[test]
+    fn should_not_accept_any_link_without_patterns() {
+        let rules: ReferenceRules = toml::from_str("required = true").unwrap();
+        assert!(rules.validate(&parse_message("fix: x (#3)")).is_empty());
+        assert!(rules
+            .validate(&parse_message("fix: x\n\nRefs: PROJ-1"))
+            .is_empty());
+        assert_eq!(
+            rules.validate(&parse_message("fix: use SHA-256")),
+            vec![ValidationError::MissingReference { patterns: vec![] }]
+        );
+        assert_eq!(
+            rules.validate(&parse_message("docs: x\n\nSee https://docs.rs/regex")),
+            vec![ValidationError::MissingReference { patterns: vec![] }]
+        );
+
+        let rules: ReferenceRules = toml::from_str(
+            "required = true\npatterns = ['^https://github\\.com/org/repo/issues/']",
+        )
+        .unwrap();
+        let message = "fix: x\n\nReported in https://github.com/org/repo/issues/7";
+        assert!(rules.validate(&parse_message(message)).is_empty());
+    }
+
+    #[test]
+    fn should_require_reference_matching_tracker() {
+        let rules: ReferenceRules =
+            toml::from_str("required = true\npatterns = ['^PROJ-\\d+$', '^#\\d+$']").unwrap();
+        assert!(rules
+            .validate(&parse_message("fix: x\n\nRefs: PROJ-1"))
+            .is_empty());
+        assert!(rules.validate(&parse_message("fix: x (#3)")).is_empty());
+
+        let errors = rules.validate(&parse_message("fix: x\n\nRefs: OTHER-1"));
+        assert_eq!(
+            errors[0].to_string(),
+            "Validation error: Missing issue reference matching one of: ^PROJ-\\d+$, ^#\\d+$."
+        );
+    }
+}
@@ -1,22 +1,241 @@
+use std::{collections::BTreeMap, path::Path};
+
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
+use crate::parser_lib::{errors::ValidationError, parsing::types::CommitMessage};
+
+use super::{
+    allowed::{AllowedEntry, AllowedList},
+    cargo_workspace::workspace_packages,
+    pattern::Pattern,
+    presets::Preset,
+};
+
+/// Where scopes are allowed from besides the `allowed` list
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScopeSource {
+    /// Package names of the Cargo workspace next to the config file
+    CargoWorkspace,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ScopeRules {
-    allowed: Vec<String>,
+    /// An empty list allows any scope, unless the scopes come from `allowed_from`
+    allowed: AllowedList,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    allowed_from: Option<ScopeSource>,
     required: bool,
+    /// Scopes implied by changed files, e.g. `"crates/api/**" = "api"`
+    #[serde(default)]
+    paths: BTreeMap<String, String>,
 }
 
-impl Default for ScopeRules {
-    fn default() -> Self {
+impl ScopeRules {
+    pub fn for_preset(_preset: Preset) -> Self {
+        // Scopes are project specific, none of the presets restrict them
         Self {
-            allowed: vec![
-                "api".to_string(),
-                "cli".to_string(),
-                "rules".to_string(),
-                "parsing".to_string(),
-                "lexing".to_string(),
-            ],
+            allowed: AllowedList::default(),
+            allowed_from: None,
             required: false,
+            paths: BTreeMap::new(),
+        }
+    }
+
+    pub fn allowed(&self) -> &AllowedList {
+        &self.allowed
+    }
+
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    /// Adds the scopes of `allowed_from`, read from the project in `root`.
+    /// Entries already in `allowed` keep their descriptions.
+    pub fn discover(&mut self, root: &Path) -> Result<(), String> {
+        match self.allowed_from {
+            Some(ScopeSource::CargoWorkspace) => {
+                for name in workspace_packages(root)? {
+                    self.allowed.add(AllowedEntry::new(&name));
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Whether scopes are mapped to paths, only then are the staged files worth reading
+    pub fn has_paths(&self) -> bool {
+        !self.paths.is_empty()
+    }
+
+    /// Scopes of the files' paths, sorted and without duplicates
+    pub fn scopes_for(&self, files: &[String]) -> Vec<String> {
+        let globs: Vec<(Pattern, &String)> = self
+            .paths
+            .iter()
+            .map(|(glob, scope)| (Pattern::from_glob(glob), scope))
+            .collect();
+        files
+            .iter()
+            .flat_map(|file| {
+                globs
+                    .iter()
+                    .filter(|(glob, _)| glob.is_match(file))
+                    .map(|(_, scope)| scope.to_string())
+            })
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    pub fn validate(&self, message: &CommitMessage) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        if let Some(scope) = &message.scope {
+            let value = scope.content_string();
+            // Scopes stay restricted when reading them from `allowed_from` failed
+            let restricted = !self.allowed.is_empty() || self.allowed_from.is_some();
+            if restricted && !self.allowed.contains(&value) {
+                let suggestion = self.allowed.closest(&value).cloned();
+                errors.push(ValidationError::UnknownScope {
+                    value,
+                    span: scope.content_span(),
+                    suggestion,
+                    allowed: self.allowed.clone(),
+                });
+            }
+        } else if self.required {
+            errors.push(ValidationError::MissingScope);
+        }
+        errors
+    }
+
+    /// Checks the scope against the scopes implied by the changed files.
+    /// Files outside of the mapped paths don't imply anything.
+    pub fn validate_paths(
+        &self,
+        message: &CommitMessage,
+        files: &[String],
+    ) -> Option<ValidationError> {
+        let scope = message.scope.as_ref()?;
+        let implied = self.scopes_for(files);
+        let value = scope.content_string();
+        if implied.is_empty() || implied.contains(&value) {
+            return None;
         }
+        Some(ValidationError::ScopeMismatch {
+            value,
+            span: scope.content_span(),
+            implied,
+        })
+    }
+}
+
+impl Default for ScopeRules {
+    fn default() -> Self {
+        Self::for_preset(Preset::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::parser_lib::test_utils::parse_message;
+
+    fn rules() -> ScopeRules {
+        toml::from_str(
+            r#"
+            allowed = []
+            required = false
+            [paths]
+            "crates/api/**" = "api"
+            "crates/cli/**" = "cli"
+            "**/*.md" = "docs"
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn files(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|path| path.to_string()).collect()
+    }
+
+    #[test]
+    fn should_map_files_to_scopes() {
+        let staged = files(&["crates/cli/src/main.rs", "README.md", "Cargo.lock"]);
+        assert_eq!(rules().scopes_for(&staged), ["cli", "docs"]);
+        assert!(rules()
+            .scopes_for(&files(&["crates/apiary/x.rs"]))
+            .is_empty());
+    }
+
+    #[test]
+    fn should_allow_cargo_workspace_packages() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"api\", \"cli\"]\n",
+        )
+        .unwrap();
+        for name in ["api", "cli"] {
+            fs::create_dir(dir.path().join(name)).unwrap();
+            let manifest = format!("[package]\nname = \"{}\"\n", name);
+            fs::write(dir.path().join(name).join("Cargo.toml"), manifest).unwrap();
+        }
+
+        let mut rules: ScopeRules = toml::from_str(
+            "allowed_from = \"cargo-workspace\"\nrequired = false\n[allowed]\napi = \"The API\"",
+        )
+        .unwrap();
+        rules.discover(dir.path()).unwrap();
+        assert_eq!(rules.allowed().names(), ["api", "cli"]);
+        assert_eq!(
+            rules.allowed().get("api").unwrap().description(),
+            Some("The API")
+        );
+    }
+
+    #[test]
+    fn should_reject_scopes_when_cargo_workspace_is_unreadable() {
+        let dir = tempdir().unwrap();
+        let mut rules: ScopeRules =
+            toml::from_str("allowed_from = \"cargo-workspace\"\nrequired = false\nallowed = []")
+                .unwrap();
+        assert!(rules.discover(dir.path()).is_err());
+        let errors = rules.validate(&parse_message("fix(api): x"));
+        assert!(
+            matches!(&errors[..], [ValidationError::UnknownScope { value, .. }] if value == "api")
+        );
+        assert!(rules.validate(&parse_message("fix: x")).is_empty());
+    }
+
+    #[test]
+    fn should_report_scope_not_implied_by_files() {
+        let staged = files(&["crates/cli/src/main.rs"]);
+        let error = rules()
+            .validate_paths(&parse_message("fix(api): x"), &staged)
+            .unwrap();
+        assert_eq!(error.span(), Some(4..7));
+        assert!(
+            matches!(&error, ValidationError::ScopeMismatch { implied, .. } if implied == &["cli"])
+        );
+        // Staged files only hint at the scope, --fix doesn't replace it
+        assert!(error.fix().is_none());
+
+        assert!(rules()
+            .validate_paths(&parse_message("fix(cli): x"), &staged)
+            .is_none());
+        assert!(rules()
+            .validate_paths(&parse_message("fix: x"), &staged)
+            .is_none());
+        let unmapped = files(&["Cargo.lock"]);
+        assert!(rules()
+            .validate_paths(&parse_message("fix(api): x"), &unmapped)
+            .is_none());
     }
 }
@@ -1,23 +1,214 @@
 use serde::{Deserialize, Serialize};
 
+use crate::parser_lib::{errors::ValidationError, parsing::types::CommitMessage};
+
+use super::{
+    allowed::{AllowedEntry, AllowedList},
+    presets::Preset,
+};
+
+// Gitmoji shortcodes of the preset emoji and a few other common ones
+const SHORTCODES: [(&str, &str); 31] = [
+    ("ambulance", "🚑"),
+    ("art", "🎨"),
+    ("arrow_down", "⬇"),
+    ("arrow_up", "⬆"),
+    ("bookmark", "🔖"),
+    ("boom", "💥"),
+    ("bug", "🐛"),
+    ("bulb", "💡"),
+    ("construction", "🚧"),
+    ("construction_worker", "👷"),
+    ("fire", "🔥"),
+    ("globe_with_meridians", "🌐"),
+    ("green_heart", "💚"),
+    ("heavy_minus_sign", "➖"),
+    ("heavy_plus_sign", "➕"),
+    ("lipstick", "💄"),
+    ("lock", "🔒"),
+    ("memo", "📝"),
+    ("pencil2", "✏"),
+    ("recycle", "♻"),
+    ("rewind", "⏪"),
+    ("rocket", "🚀"),
+    ("rotating_light", "🚨"),
+    ("see_no_evil", "🙈"),
+    ("sparkles", "✨"),
+    ("tada", "🎉"),
+    ("truck", "🚚"),
+    ("wheelchair", "♿"),
+    ("white_check_mark", "✅"),
+    ("wrench", "🔧"),
+    ("zap", "⚡"),
+];
+
+// Emoji are compared without the variation selector, `♻️` and `♻` are the same
+fn normalize_emoji(emoji: &str) -> String {
+    emoji.replace('\u{fe0f}', "")
+}
+
+fn is_shortcode(value: &str) -> bool {
+    value.len() > 2 && value.starts_with(':') && value.ends_with(':')
+}
+
+fn is_emoji(value: &str) -> bool {
+    value
+        .chars()
+        .next()
+        .is_some_and(|c| !c.is_ascii() && !c.is_alphanumeric())
+}
+
+fn emoji_matches(entry: &AllowedEntry, value: &str) -> bool {
+    let Some(emoji) = entry.emoji() else {
+        return false;
+    };
+    if emoji == value {
+        return true;
+    }
+    let value = match value.strip_prefix(':').and_then(|v| v.strip_suffix(':')) {
+        Some(name) => match SHORTCODES.iter().find(|(shortcode, _)| *shortcode == name) {
+            Some((_, emoji)) => emoji,
+            None => return false,
+        },
+        None => value,
+    };
+    normalize_emoji(emoji) == normalize_emoji(value)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TopicRules {
-    allowed: Vec<String>,
+    allowed: AllowedList,
     required: bool,
+    /// Accept the emoji of an allowed topic, as `✨` or `:sparkles:`, in place of its name
+    #[serde(default)]
+    emoji: bool,
 }
 
-impl Default for TopicRules {
-    fn default() -> Self {
+impl TopicRules {
+    pub fn for_preset(preset: Preset) -> Self {
+        let entries: Vec<AllowedEntry> = [
+            (
+                "build",
+                "Changes that affect the build system or external dependencies",
+                "Builds",
+                "👷",
+            ),
+            (
+                "chore",
+                "Other changes that don't modify src or test files",
+                "Chores",
+                "🔧",
+            ),
+            (
+                "ci",
+                "Changes to CI configuration files and scripts",
+                "Continuous Integration",
+                "💚",
+            ),
+            ("docs", "Documentation only changes", "Documentation", "📝"),
+            ("feat", "A new feature", "Features", "✨"),
+            ("fix", "A bug fix", "Bug Fixes", "🐛"),
+            (
+                "perf",
+                "A code change that improves performance",
+                "Performance Improvements",
+                "⚡",
+            ),
+            (
+                "refactor",
+                "A code change that neither fixes a bug nor adds a feature",
+                "Code Refactoring",
+                "♻",
+            ),
+            ("revert", "Reverts a previous commit", "Reverts", "⏪"),
+            (
+                "style",
+                "Changes that do not affect the meaning of the code (white-space, formatting, etc)",
+                "Styles",
+                "🎨",
+            ),
+            (
+                "test",
+                "Adding missing tests or correcting existing tests",
+                "Tests",
+                "✅",
+            ),
+        ]
+        .iter()
+        .filter(|(name, ..)| match preset {
+            Preset::Conventional | Preset::Gitmoji => true,
+            Preset::Angular => !matches!(*name, "chore" | "style"),
+        })
+        .map(|(name, description, title, emoji)| {
+            AllowedEntry::described(name, description, title, emoji)
+        })
+        .collect();
+
         Self {
-            allowed: vec![
-                "chore".to_string(),
-                "feat".to_string(),
-                "fix".to_string(),
-                "refactor".to_string(),
-                "test".to_string(),
-                "docs".to_string(),
-            ],
+            allowed: AllowedList::new(entries),
             required: true,
+            emoji: preset == Preset::Gitmoji,
         }
     }
+
+    pub fn allowed(&self) -> &AllowedList {
+        &self.allowed
+    }
+
+    pub fn validate(&self, message: &CommitMessage) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        if let Some(topic) = message.topic.as_ref() {
+            let value = topic.content_string();
+            let is_allowed_emoji = (is_shortcode(&value) || is_emoji(&value))
+                && self.emoji
+                && self
+                    .allowed
+                    .iter()
+                    .any(|entry| emoji_matches(entry, &value));
+            if !self.allowed.is_empty() && !self.allowed.contains(&value) && !is_allowed_emoji {
+                let suggestion = self.allowed.closest(&value).cloned();
+                errors.push(ValidationError::UnknownTopic {
+                    value,
+                    span: topic.content_span(),
+                    suggestion,
+                    allowed: self.allowed.clone(),
+                });
+            }
+        }
+        errors
+    }
+}
+
+impl Default for TopicRules {
+    fn default() -> Self {
+        Self::for_preset(Preset::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser_lib::test_utils::parse_message;
+
+    #[test]
+    fn should_accept_gitmoji_topics_when_enabled() {
+        let rules = TopicRules::for_preset(Preset::Gitmoji);
+        for message in [":sparkles: add users", "✨ add users", "♻️ split parser"] {
+            assert!(
+                rules.validate(&parse_message(message)).is_empty(),
+                "{}",
+                message
+            );
+        }
+        assert!(matches!(
+            &rules.validate(&parse_message(":poop: add users"))[0],
+            ValidationError::UnknownTopic { value, .. } if value == ":poop:"
+        ));
+
+        let rules = TopicRules::for_preset(Preset::Conventional);
+        assert_eq!(
+            rules.validate(&parse_message(":sparkles: add users")).len(),
+            1
+        );
+    }
 }
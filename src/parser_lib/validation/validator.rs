@@ -1,19 +1,22 @@
-use crate::parser_lib::parsing::types::CommitMessage;
+use crate::parser_lib::{errors::ValidationError, parsing::types::CommitMessage};
 
 use super::rules::ValidationRules;
 
 pub struct Validator {}
 
 impl Validator {
-    pub fn validate(msg: &CommitMessage, rules: ValidationRules) -> () {
-        println!("Validating commit message: {:?}", msg);
-        println!("Using rules: {:?}", rules);
+    pub fn validate(msg: &CommitMessage, rules: &ValidationRules) -> Vec<ValidationError> {
+        rules.validate(msg)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser_lib::{
+        test_utils::{parse_message, rules_from},
+        validation::{try_parse_config, Fix},
+    };
 
     #[test]
     fn test_validate() {
@@ -23,8 +26,84 @@ mod tests {
             description: None,
             body: None,
             footers: None,
+            diagnostics: Vec::new(),
+            breaking_footer: false,
         };
         let rules = ValidationRules::default();
-        Validator::validate(&msg, rules);
+        assert!(Validator::validate(&msg, &rules).is_empty());
+    }
+
+    #[test]
+    fn should_accept_allowed_topic() {
+        let msg = parse_message("feat(api): add users endpoint");
+        assert!(Validator::validate(&msg, &ValidationRules::default()).is_empty());
+    }
+
+    #[test]
+    fn should_describe_allowed_topics_on_unknown_topic() {
+        let msg = parse_message("feature: add users endpoint");
+        let errors = Validator::validate(&msg, &ValidationRules::default());
+        assert_eq!(errors.len(), 1);
+        assert!(
+            matches!(&errors[0], ValidationError::UnknownTopic { span, .. } if *span == (0..7))
+        );
+        let message = errors[0].to_string();
+        assert!(message.starts_with("Validation error: Unknown topic 'feature'."));
+        assert!(message.ends_with("Did you mean 'feat' — A new feature?"));
+    }
+
+    #[test]
+    fn should_list_allowed_topics_when_nothing_is_close() {
+        let msg = parse_message("banana: add users endpoint");
+        let errors = Validator::validate(&msg, &ValidationRules::default());
+        assert!(errors[0]
+            .to_string()
+            .contains("Expected one of:\n  'build'"));
+        assert!(errors[0].fix().is_none());
+    }
+
+    #[test]
+    fn should_apply_rules_for_topic() {
+        let rules = rules_from(
+            "scope.required = true\n\
+             [when.topic.feat]\nbody.required = true\n\
+             [when.topic.chore]\nscope.required = false\n",
+        );
+
+        let errors = Validator::validate(&parse_message("feat(api): add users endpoint"), &rules);
+        assert_eq!(errors, vec![ValidationError::MissingBody]);
+        let msg = parse_message("feat(api): add users endpoint\n\nso clients can list them");
+        assert!(Validator::validate(&msg, &rules).is_empty());
+
+        assert!(Validator::validate(&parse_message("chore: bump deps"), &rules).is_empty());
+        let errors = Validator::validate(&parse_message("fix: handle empty input"), &rules);
+        assert_eq!(errors, vec![ValidationError::MissingScope]);
+    }
+
+    #[test]
+    fn should_skip_header_rules_for_reverts_generated_by_git() {
+        let rules = rules_from("scope.required = true");
+
+        let msg = parse_message(
+            "Revert \"feat(api): add users endpoint\"\n\n\
+             This reverts commit 1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b.",
+        );
+        assert!(Validator::validate(&msg, &rules).is_empty());
+        let errors = Validator::validate(&parse_message("revert: undo users endpoint"), &rules);
+        assert_eq!(errors, vec![ValidationError::MissingScope]);
+    }
+
+    #[test]
+    fn should_reject_invalid_rules_for_topic() {
+        let err =
+            try_parse_config("[when.topic.feat]\nbody.required = 'yes'\n".to_string()).unwrap_err();
+        assert!(err.contains("Invalid rules for topic 'feat'"));
+    }
+
+    #[test]
+    fn should_offer_suggestion_as_fix() {
+        let msg = parse_message("fature(api): add users endpoint");
+        let errors = Validator::validate(&msg, &ValidationRules::default());
+        assert_eq!(errors[0].fix(), Some(Fix::new(0..6, "feat")));
     }
 }
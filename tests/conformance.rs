@@ -0,0 +1,93 @@
+//! Conventional Commits 1.0.0 conformance suite
+//!
+//! Every `<name>.msg` in `tests/fixtures/conformance` has a `<name>.json` next to it, holding the
+//! spec clause the message covers and either the expected parse tree or the expected syntax error.
+//! Errors are compared by kind, the unexpected token and its position, not by their wording.
+
+use std::{fs, path::Path};
+
+use parser_lib::{CommitMessage, CommitMessageParser, SyntaxError};
+use serde_json::{json, Value};
+
+fn tree(message: &CommitMessage) -> Value {
+    let footers: Vec<Value> = message
+        .footers()
+        .iter()
+        .map(|footer| {
+            json!({
+                "key": footer.footer_key(),
+                "value": footer.footer_value(),
+            })
+        })
+        .collect();
+    json!({
+        "topic": message.topic().map(|topic| topic.content_str()),
+        "scope": message.scope().map(|scope| scope.content_str()),
+        "breaking": message.is_breaking(),
+        "description": message.description().map(|description| description.content_str()),
+        "body": message.body().map(|body| body.content_str()),
+        "footers": footers,
+    })
+}
+
+fn outcome(message: &str) -> Value {
+    match CommitMessageParser::new().parse(message) {
+        Ok(parsed) => json!({ "parsed": tree(&parsed) }),
+        Err(SyntaxError::UnexpectedToken(unexpected)) => json!({
+            "error": {
+                "kind": "unexpected-token",
+                "found": unexpected.found(),
+                "line": unexpected.position().line,
+                "column": unexpected.position().column,
+            }
+        }),
+        Err(SyntaxError::UnexpectedEndOfFile) => {
+            json!({ "error": { "kind": "unexpected-end-of-file" } })
+        }
+        Err(err) => json!({ "error": { "kind": err.to_string() } }),
+    }
+}
+
+#[test]
+fn should_conform_to_the_spec() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/conformance");
+    let mut failures = Vec::new();
+    let mut checked = 0;
+    for entry in fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("msg") {
+            continue;
+        }
+        let message = fs::read_to_string(&path).unwrap();
+        let fixture: Value =
+            serde_json::from_str(&fs::read_to_string(path.with_extension("json")).unwrap())
+                .unwrap();
+
+        let mut expected = fixture.clone();
+        let clause = expected.as_object_mut().unwrap().remove("clause").unwrap();
+        let actual = outcome(&message);
+        if actual != expected {
+            failures.push(format!(
+                "{:?} (clause {})\nexpected: {}\nactual:   {}",
+                path.file_name().unwrap(),
+                clause,
+                expected,
+                actual
+            ));
+        }
+        // The header-only parse has to agree on whether the commit is breaking
+        if let Some(breaking) = expected.pointer("/parsed/breaking") {
+            let header = CommitMessageParser::new().parse_header(&message).unwrap();
+            if Value::Bool(header.is_breaking()) != *breaking {
+                failures.push(format!(
+                    "{:?} (clause {})\nparse_header disagrees on breaking",
+                    path.file_name().unwrap(),
+                    clause
+                ));
+            }
+        }
+        checked += 1;
+    }
+    assert!(checked > 0);
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
@@ -0,0 +1,9 @@
+#!/bin/sh
+# Regenerates the expected output of every fixture message with git.
+# `.git` uses git's default separators, `.hash.git` also accepts '#' like `Refs #123`.
+cd "$(dirname "$0")" || exit 1
+for message in *.msg; do
+	name="${message%.msg}"
+	git -c trailer.separators=: interpret-trailers --parse <"$message" >"$name.git"
+	git -c trailer.separators=:# interpret-trailers --parse <"$message" >"$name.hash.git"
+done